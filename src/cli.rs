@@ -1,8 +1,15 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    #[arg(long, global = true)]
+    pub json_errors: bool,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -11,6 +18,12 @@ pub struct Cli {
 pub struct List {
     #[arg(long)]
     pub format: Option<String>,
+    #[arg(long)]
+    pub long: bool,
+    #[arg(long)]
+    pub sort: Option<String>,
+    #[arg(long)]
+    pub filter: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -19,15 +32,201 @@ pub struct ListTargets {
     pub format: Option<String>,
 }
 
+#[derive(Parser)]
+pub struct Apply {
+    #[arg(
+        required_unless_present = "palette_file",
+        conflicts_with = "palette_file"
+    )]
+    pub palette: Option<String>,
+    #[arg(long)]
+    pub palette_file: Option<PathBuf>,
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long, conflicts_with_all = ["dry_run", "output_dir"])]
+    pub check: bool,
+    #[arg(long)]
+    pub parallel: bool,
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+    #[arg(long, value_delimiter = ',', conflicts_with = "except")]
+    pub only: Vec<String>,
+    #[arg(long, value_delimiter = ',')]
+    pub except: Vec<String>,
+    #[arg(long)]
+    pub no_reload: bool,
+    #[arg(long, conflicts_with_all = ["dry_run", "check"])]
+    pub force: bool,
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
 #[derive(Parser)]
 pub struct Validate {
     pub target: Option<String>,
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Parser)]
+pub struct Doctor {
+    #[arg(long)]
+    pub fix: bool,
+}
+
+#[derive(Parser)]
+pub struct Init {
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Parser)]
+pub struct AddTarget {
+    #[arg(long)]
+    pub name: String,
+    #[arg(long)]
+    pub template: String,
+    #[arg(long, default_value = "")]
+    pub output: String,
+    #[arg(long, default_value = "include")]
+    pub mode: String,
+    #[arg(long, default_value = "")]
+    pub reload_cmd: String,
+}
+
+#[derive(Parser)]
+pub struct RemoveTarget {
+    pub name: String,
+}
+
+#[derive(Parser)]
+pub struct Preview {
+    pub target: String,
+    #[arg(long)]
+    pub palette: String,
+}
+
+#[derive(Parser)]
+pub struct Render {
+    #[arg(long)]
+    pub palette: String,
+    #[arg(long)]
+    pub template_file: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+pub struct Diff {
+    #[arg(long)]
+    pub palette: String,
+}
+
+#[derive(Parser)]
+pub struct Watch {
+    #[arg(long)]
+    pub palette: String,
+    #[arg(long)]
+    pub templates: bool,
+}
+
+#[derive(Parser)]
+pub struct Generate {
+    #[arg(long)]
+    pub from_wallpaper: String,
+    #[arg(long)]
+    pub name: String,
+}
+
+#[derive(Parser)]
+pub struct Edit {
+    #[arg(long)]
+    pub palette: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct Random {
+    #[arg(long)]
+    pub seed: Option<u64>,
+    #[arg(long)]
+    pub exclude_current: bool,
+}
+
+#[derive(Parser)]
+pub struct Revert {}
+
+#[derive(Parser)]
+pub struct Export {
+    pub palette: String,
+    #[arg(long, default_value = "themer-json")]
+    pub format: String,
+    #[arg(long)]
+    pub out: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct Check {}
+
+#[derive(Parser)]
+pub struct NewPalette {
+    #[arg(long)]
+    pub name: String,
+    #[arg(long, default_value = "both")]
+    pub base: String,
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Parser)]
+pub struct ScanTemplates {
+    #[arg(long)]
+    pub apply: bool,
+}
+
+#[derive(Parser)]
+pub struct Completion {
+    pub shell: clap_complete::Shell,
+}
+
+#[derive(Parser)]
+pub struct Cache {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    Clear(CacheClear),
+}
+
+#[derive(Parser)]
+pub struct CacheClear {
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     List(List),
     ListTargets(ListTargets),
-    Apply { palette: String },
+    Apply(Apply),
     Validate(Validate),
+    Doctor(Doctor),
+    Init(Init),
+    AddTarget(AddTarget),
+    RemoveTarget(RemoveTarget),
+    Preview(Preview),
+    Render(Render),
+    Diff(Diff),
+    Watch(Watch),
+    Generate(Generate),
+    Edit(Edit),
+    Random(Random),
+    Revert(Revert),
+    Export(Export),
+    Check(Check),
+    NewPalette(NewPalette),
+    Cache(Cache),
+    ScanTemplates(ScanTemplates),
+    Completion(Completion),
+    #[command(name = "__complete-palettes", hide = true)]
+    CompletePalettes,
 }
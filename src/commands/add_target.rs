@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::{Mode, Target};
+use crate::output::output;
+
+pub fn execute(
+    name: &str,
+    template: &str,
+    output_path: &str,
+    mode: &str,
+    reload_cmd: &str,
+) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    let target = Target {
+        name: name.to_string(),
+        template: template.to_string(),
+        output: output_path.to_string(),
+        mode: mode.parse::<Mode>()?,
+        reload_cmd: reload_cmd.to_string(),
+        ..Default::default()
+    };
+
+    run(&config_loader, target)
+}
+
+fn run(config_loader: &ConfigLoader, target: Target) -> Result<()> {
+    let mut config = config_loader.load()?;
+
+    if config.targets.iter().any(|t| t.name == target.name) {
+        anyhow::bail!("Target '{}' already exists", target.name);
+    }
+
+    if target.mode != Mode::Include && target.output.is_empty() {
+        anyhow::bail!(
+            "Target '{}' with mode '{}' requires --output",
+            target.name,
+            target.mode
+        );
+    }
+
+    output::item(Some("Target"), &target.name, Some(&target.template));
+    config.targets.push(target);
+    config_loader.save(&config)?;
+
+    output::success("Target added");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::setup_config_dir;
+
+    fn create_target(name: &str) -> Target {
+        Target {
+            name: name.to_string(),
+            template: "kitty.conf".to_string(),
+            output: String::new(),
+            mode: Mode::Include,
+            reload_cmd: "kill -SIGUSR1 $(pgrep kitty)".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_add_target_persists() {
+        let (_temp, loader) = setup_config_dir();
+
+        run(&loader, create_target("kitty")).unwrap();
+
+        let config = loader.load().unwrap();
+        assert_eq!(config.targets.len(), 1);
+        assert_eq!(config.targets[0].name, "kitty");
+        assert_eq!(config.targets[0].template, "kitty.conf");
+    }
+
+    #[test]
+    fn test_add_target_duplicate_name_fails() {
+        let (_temp, loader) = setup_config_dir();
+        run(&loader, create_target("kitty")).unwrap();
+
+        let result = run(&loader, create_target("kitty"));
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+        assert_eq!(loader.load().unwrap().targets.len(), 1);
+    }
+
+    #[test]
+    fn test_add_target_replace_requires_output() {
+        let (_temp, loader) = setup_config_dir();
+        let mut target = create_target("alacritty");
+        target.mode = Mode::Replace;
+
+        let result = run(&loader, target);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("requires --output")
+        );
+    }
+}
@@ -1,41 +1,1194 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::thread;
 
 use crate::config::loader::ConfigLoader;
+use crate::config::models::{Mode, Target};
 use crate::output::output;
 use crate::palette::loader::PaletteLoader;
-use crate::target::processor::TargetProcessor;
+use crate::palette::models::Palette;
+use crate::target::processor::{TargetProcessor, placeholder_problems, template_parts};
 use crate::template::engine::TemplateEngine;
 
-pub fn execute(palette_name: &str) -> Result<()> {
-    output::header(&format!("Applying palette: {}", palette_name));
+#[derive(Debug, Default)]
+pub struct ApplyOptions {
+    pub dry_run: bool,
+    /// Renders replace-mode targets and fails if any differs from the file
+    /// on disk. Nothing is written and no commands run.
+    pub check: bool,
+    pub parallel: bool,
+    /// Writes every target into this directory instead of its configured
+    /// location, without running reload commands.
+    pub output_dir: Option<PathBuf>,
+    /// When non-empty, only these targets are processed.
+    pub only: Vec<String>,
+    /// Targets to leave untouched.
+    pub except: Vec<String>,
+    /// Loads the palette from this file instead of the palettes directory.
+    /// The palette name passed to `run` is ignored, and `active_palette`
+    /// is left unchanged since the file isn't installed.
+    pub palette_file: Option<PathBuf>,
+    /// A palette read from stdin (see [`read_palette`]), applied like
+    /// `palette_file`: the palettes directory isn't touched and
+    /// `active_palette` is left unchanged.
+    pub piped_palette: Option<Palette>,
+    /// Writes targets as usual but skips their reload commands, e.g. when
+    /// no desktop session is running.
+    pub no_reload: bool,
+    /// Writes and reloads every target, even those whose output is
+    /// unchanged.
+    pub force: bool,
+}
+
+/// Palette argument that makes `apply` read the palette from stdin.
+pub const STDIN_PALETTE: &str = "-";
 
+/// Applies `palette_name`, or the palette piped in on stdin when it is `-`.
+/// `name` renames a piped palette.
+pub fn execute(palette_name: &str, name: Option<&str>, mut options: ApplyOptions) -> Result<()> {
     let config_loader = ConfigLoader::new()?;
+
+    if palette_name == STDIN_PALETTE {
+        options.piped_palette = Some(read_palette(io::stdin().lock(), name)?);
+    } else if name.is_some() {
+        anyhow::bail!("--name only applies when reading a palette from stdin (-)");
+    }
+
+    run(&config_loader, palette_name, &options)
+}
+
+/// Reads a palette as JSON, e.g. piped from another program. `name`
+/// replaces the palette's own name; a palette without either is called
+/// `stdin`. Like `--palette-file`, it can't inherit from a `base`.
+pub fn read_palette(reader: impl Read, name: Option<&str>) -> Result<Palette> {
+    let mut value: serde_json::Value =
+        serde_json::from_reader(reader).context("Failed to parse palette JSON from stdin")?;
+
+    if let Some(object) = value.as_object_mut() {
+        match name {
+            Some(name) => {
+                object.insert("name".to_string(), name.into());
+            }
+            None => {
+                object.entry("name").or_insert_with(|| "stdin".into());
+            }
+        }
+    }
+    if value.get("base").is_some() {
+        anyhow::bail!("Palettes read from stdin can't set 'base'");
+    }
+
+    let mut palette: Palette =
+        serde_json::from_value(value).context("Failed to parse palette from stdin")?;
+    palette.normalize().context("Invalid palette from stdin")?;
+    palette.validate().context("Invalid palette from stdin")?;
+
+    Ok(palette)
+}
+
+/// Applies `palette_name` to every configured target. Shared with `watch`,
+/// which reapplies on each change.
+pub(crate) fn run(
+    config_loader: &ConfigLoader,
+    palette_name: &str,
+    options: &ApplyOptions,
+) -> Result<()> {
+    let dry_run = options.dry_run;
+
+    if !options.only.is_empty() && !options.except.is_empty() {
+        anyhow::bail!("--only and --except cannot be used together");
+    }
+
     let mut config = config_loader.load()?;
 
-    let palette_loader = PaletteLoader::new(config_loader.config_dir());
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
 
-    let palette = palette_loader
-        .load(palette_name)
-        .with_context(|| format!("Palette '{}' not found", palette_name))?;
+    let (palette_name, palette) = match (&options.piped_palette, &options.palette_file) {
+        (Some(palette), _) => {
+            output::header(&format!("Applying palette from stdin: {}", palette.name));
+            (palette.name.as_str(), palette.clone())
+        }
+        (None, Some(path)) => {
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            output::header(&format!("Applying palette file: {}", path.display()));
+            let palette = Palette::from_path(path)
+                .with_context(|| format!("Failed to load palette file '{}'", path.display()))?;
+            (name, palette)
+        }
+        (None, None) => {
+            output::header(&format!("Applying palette: {}", palette_name));
+            // Aliases are resolved up front so the real palette name is what
+            // gets recorded as active.
+            let palette_name = palette_loader.resolve(palette_name);
+            let palette = palette_loader
+                .load(palette_name)
+                .with_context(|| format!("Failed to load palette '{}'", palette_name))?;
+            (palette_name, palette)
+        }
+    };
 
     if config.targets.is_empty() {
         output::warning("No targets configured");
         return Ok(());
     }
 
+    let targets = select_targets(
+        &order_targets(&config.targets)?,
+        &options.only,
+        &options.except,
+    );
+    for problem in targets
+        .iter()
+        .flat_map(|target| placeholder_problems(target))
+    {
+        output::warning(&problem);
+    }
+
     let engine = TemplateEngine::new();
-    let context = engine.create_context(&palette)?;
-    let mut processor = TargetProcessor::new(config_loader.config_dir());
+    let context = engine.create_target_context(&palette, palette_name, &config, &palette_loader)?;
+    let new_processor = || {
+        let mut processor =
+            TargetProcessor::new(config_loader.config_dir()).with_cache_dir(&config.cache_dir);
+        if options.no_reload {
+            processor = processor.without_reload();
+        }
+        if options.force {
+            processor = processor.with_force();
+        }
+        match &options.output_dir {
+            Some(dir) => processor.with_output_dir(dir.clone()),
+            None => processor,
+        }
+    };
+    let mut processor = new_processor();
 
-    for target in &config.targets {
-        if let Err(e) = processor.process(target, &context, &palette) {
-            output::error(&format!("Failed to process {}: {}", target.name, e));
+    if options.check {
+        return check_up_to_date(&mut processor, &targets, &context);
+    }
+
+    let failed = if options.parallel {
+        // Each worker gets its own processor since the template engine is
+        // stateful. Output is captured per target and replayed in order.
+        // Batches run one after another so `after` is still honored.
+        let mut failed = 0;
+        let mut position = 0;
+        for batch in parallel_batches(&targets) {
+            thread::scope(|scope| {
+                let workers: Vec<_> = batch
+                    .iter()
+                    .enumerate()
+                    .map(|(index, target)| {
+                        let (context, palette) = (&context, &palette);
+                        let new_processor = &new_processor;
+                        let progress = (position + index + 1, targets.len());
+                        scope.spawn(move || {
+                            let mut processor = new_processor();
+                            output::capture(|| {
+                                process_target(
+                                    &mut processor,
+                                    target,
+                                    context,
+                                    palette,
+                                    dry_run,
+                                    progress,
+                                )
+                            })
+                        })
+                    })
+                    .collect();
+
+                for worker in workers {
+                    match worker.join() {
+                        Ok((succeeded, lines)) => {
+                            output::replay(lines);
+                            if !succeeded {
+                                failed += 1;
+                            }
+                        }
+                        Err(_) => {
+                            output::error("A target worker thread panicked");
+                            failed += 1;
+                        }
+                    }
+                }
+            });
+            position += batch.len();
         }
+        failed
+    } else {
+        targets
+            .iter()
+            .enumerate()
+            .filter(|&(index, target)| {
+                let progress = (index + 1, targets.len());
+                !process_target(
+                    &mut processor,
+                    target,
+                    &context,
+                    &palette,
+                    dry_run,
+                    progress,
+                )
+            })
+            .count()
+    };
+
+    if dry_run {
+        output::info("Dry run: no files were written");
+        return check_failures(failed);
     }
 
-    config.active_palette = palette_name.to_string();
-    config_loader.save(&config)?;
+    if let Some(dir) = &options.output_dir {
+        if failed == 0 {
+            output::success(&format!("Rendered targets into {}", dir.display()));
+        }
+        return check_failures(failed);
+    }
+
+    processor.run_post_apply_command(&config.post_apply_cmd, palette_name)?;
+
+    if options.piped_palette.is_some() {
+        output::info("Applied from stdin: active_palette left unchanged");
+    } else if options.palette_file.is_some() {
+        output::info("Applied from a file: active_palette left unchanged");
+    } else {
+        if config.active_palette != palette_name {
+            config.previous_palette = std::mem::take(&mut config.active_palette);
+        }
+        config.active_palette = palette_name.to_string();
+        config_loader.save(&config)?;
+    }
 
+    check_failures(failed)?;
     output::success("Theme applied successfully!");
     Ok(())
 }
+
+/// Turns a count of failed targets into an error, so callers exit non-zero
+/// after every other target has still been processed.
+fn check_failures(failed: usize) -> Result<()> {
+    if failed > 0 {
+        anyhow::bail!("{} target(s) failed to apply", failed);
+    }
+    Ok(())
+}
+
+/// Orders `targets` so that each one comes after the targets named in its
+/// `after`, keeping declaration order where they don't constrain it. Fails on
+/// unknown target names and on cycles.
+fn order_targets(targets: &[Target]) -> Result<Vec<&Target>> {
+    for target in targets {
+        if let Some(unknown) = target
+            .after
+            .iter()
+            .find(|name| !targets.iter().any(|t| t.name == **name))
+        {
+            anyhow::bail!(
+                "Target '{}' runs after unknown target '{}'",
+                target.name,
+                unknown
+            );
+        }
+    }
+
+    let mut ordered: Vec<&Target> = Vec::with_capacity(targets.len());
+    let mut pending: Vec<&Target> = targets.iter().collect();
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|target| {
+            target
+                .after
+                .iter()
+                .all(|name| ordered.iter().any(|done| done.name == *name))
+        });
+
+        match ready {
+            Some(index) => ordered.push(pending.remove(index)),
+            None => {
+                let names: Vec<&str> = pending.iter().map(|t| t.name.as_str()).collect();
+                anyhow::bail!(
+                    "Targets have cyclic 'after' dependencies: {}",
+                    names.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(ordered)
+}
+
+/// Splits targets, already in [`order_targets`] order, into batches that
+/// can each run in parallel: a target goes one batch past the latest of the
+/// targets it runs after. Dependencies that aren't being applied are ignored.
+fn parallel_batches<'a>(targets: &[&'a Target]) -> Vec<Vec<&'a Target>> {
+    let mut batches: Vec<Vec<&Target>> = Vec::new();
+    let mut batch_of: HashMap<&str, usize> = HashMap::new();
+
+    for &target in targets {
+        let batch = target
+            .after
+            .iter()
+            .filter_map(|name| batch_of.get(name.as_str()))
+            .map(|&batch| batch + 1)
+            .max()
+            .unwrap_or(0);
+
+        batch_of.insert(&target.name, batch);
+        if batch == batches.len() {
+            batches.push(Vec::new());
+        }
+        batches[batch].push(target);
+    }
+
+    batches
+}
+
+/// Narrows `targets` down to the ones named in `only` (when non-empty) and
+/// drops the ones named in `except`, warning about names that match nothing.
+fn select_targets<'a>(
+    targets: &[&'a Target],
+    only: &[String],
+    except: &[String],
+) -> Vec<&'a Target> {
+    for name in only.iter().chain(except) {
+        if !targets.iter().any(|target| target.name == *name) {
+            output::warning(&format!("Unknown target '{}'", name));
+        }
+    }
+
+    targets
+        .iter()
+        .copied()
+        .filter(|target| only.is_empty() || only.contains(&target.name))
+        .filter(|target| !except.contains(&target.name))
+        .collect()
+}
+
+/// Compares each enabled replace-mode target's rendered output with the file
+/// on disk, failing with the names of the targets that are out of date.
+fn check_up_to_date(
+    processor: &mut TargetProcessor,
+    targets: &[&Target],
+    context: &tera::Context,
+) -> Result<()> {
+    let mut stale = Vec::new();
+
+    for target in targets {
+        if !target.enabled || target.mode != Mode::Replace {
+            continue;
+        }
+
+        for part in template_parts(target) {
+            let (rendered, output_path) = processor.render_output(&part, context)?;
+            if fs::read_to_string(&output_path).ok().as_deref() == Some(rendered.as_str()) {
+                output::item(Some("="), &part.name, Some("up to date"));
+            } else {
+                output::item(
+                    Some("~"),
+                    &part.name,
+                    Some(&output_path.display().to_string()),
+                );
+                stale.push(part.name.clone());
+            }
+        }
+    }
+
+    if !stale.is_empty() {
+        anyhow::bail!(
+            "{} target(s) out of date: {}",
+            stale.len(),
+            stale.join(", ")
+        );
+    }
+
+    output::success("All targets are up to date");
+    Ok(())
+}
+
+/// Processes a single target, reporting its outcome. `(k, n)` is shown as a
+/// `[k/n]` counter before the target's own output. Returns `false` if it
+/// failed; disabled targets count as succeeded.
+fn process_target(
+    processor: &mut TargetProcessor,
+    target: &Target,
+    context: &tera::Context,
+    palette: &Palette,
+    dry_run: bool,
+    (k, n): (usize, usize),
+) -> bool {
+    let counter = format!("{}/{}", k, n);
+
+    if !target.enabled {
+        output::item(Some(&counter), &target.name, Some("skipped (disabled)"));
+        return true;
+    }
+
+    output::item(Some(&counter), &target.name, None);
+
+    let result = if dry_run {
+        processor.process_dry_run(target, context)
+    } else {
+        processor.process(target, context, palette)
+    };
+
+    if let Err(e) = &result {
+        output::error(&format!("Failed to process {}: {}", target.name, e));
+    }
+    result.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{
+        SAMPLE_BASE16, app_target, sample_palette, setup_config_dir, write_palette, write_template,
+    };
+    use crate::config::models::Config;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader, PathBuf) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_palette(&loader, "test", &sample_palette("Test"));
+        write_template(&loader, "colors.conf", "background {{ base00 }}");
+
+        let output_path = temp_dir.path().join("out").join("colors.conf");
+        let config = Config {
+            active_palette: "previous".to_string(),
+            targets: vec![Target {
+                header: false,
+                ..app_target(&output_path)
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        (temp_dir, loader, output_path)
+    }
+
+    #[test]
+    fn test_apply_writes_output_and_saves_active_palette() {
+        let (_temp, loader, output_path) = setup_test_env();
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 000000"
+        );
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+    }
+
+    #[test]
+    fn test_apply_dry_run_writes_nothing() {
+        let (_temp, loader, output_path) = setup_test_env();
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                dry_run: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(!output_path.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "previous");
+    }
+
+    #[test]
+    fn test_apply_skips_disabled_target() {
+        let (_temp, loader, output_path) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config.targets[0].enabled = false;
+        loader.save(&config).unwrap();
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert!(!output_path.exists());
+    }
+
+    #[test]
+    fn test_apply_runs_post_apply_cmd_after_targets() {
+        let (temp, loader, output_path) = setup_test_env();
+        let marker = temp.path().join("post-apply");
+        let mut config = loader.load().unwrap();
+        config.post_apply_cmd = format!(
+            "cat {} > {} && echo {{palette}} >> {}",
+            output_path.display(),
+            marker.display(),
+            marker.display()
+        );
+        loader.save(&config).unwrap();
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&marker).unwrap(),
+            "background 000000test\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_skips_empty_post_apply_cmd() {
+        let (_temp, loader, output_path) = setup_test_env();
+        assert!(loader.load().unwrap().post_apply_cmd.is_empty());
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert!(output_path.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+    }
+
+    #[test]
+    fn test_apply_parallel_writes_every_target() {
+        let (temp, loader, _) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config.targets = (0..8)
+            .map(|i| Target {
+                name: format!("app{}", i),
+                header: false,
+                ..app_target(&temp.path().join("out").join(format!("colors{}.conf", i)))
+            })
+            .collect();
+        loader.save(&config).unwrap();
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                parallel: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for target in &config.targets {
+            assert_eq!(
+                fs::read_to_string(&target.output).unwrap(),
+                "background 000000"
+            );
+        }
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+    }
+
+    #[test]
+    fn test_apply_reports_progress_counter() {
+        let (temp, loader, _) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config.targets = (0..3)
+            .map(|i| Target {
+                name: format!("app{}", i),
+                enabled: i != 1,
+                ..app_target(&temp.path().join(format!("colors{}.conf", i)))
+            })
+            .collect();
+        loader.save(&config).unwrap();
+
+        let _lock = output::QUIET_TEST_LOCK.lock().unwrap();
+        let (result, lines) = output::capture(|| run(&loader, "test", &ApplyOptions::default()));
+        result.unwrap();
+
+        let counters: Vec<&str> = lines
+            .iter()
+            .map(|line| line.text())
+            .filter(|text| text.contains("/3"))
+            .collect();
+        assert_eq!(counters.len(), 3);
+        assert!(counters[0].contains("1/3") && counters[0].contains("app0"));
+        assert!(counters[1].contains("2/3") && counters[1].contains("skipped"));
+        assert!(counters[2].contains("3/3") && counters[2].contains("app2"));
+    }
+
+    #[test]
+    fn test_apply_check_passes_when_up_to_date() {
+        let (_temp, loader, output_path) = setup_test_env();
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        let config_before = fs::read_to_string(loader.config_dir().join("config.toml")).unwrap();
+
+        let options = ApplyOptions {
+            check: true,
+            ..Default::default()
+        };
+        run(&loader, "test", &options).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 000000"
+        );
+        assert_eq!(
+            fs::read_to_string(loader.config_dir().join("config.toml")).unwrap(),
+            config_before
+        );
+    }
+
+    #[test]
+    fn test_apply_check_fails_on_stale_output() {
+        let (_temp, loader, output_path) = setup_test_env();
+        fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+        fs::write(&output_path, "background 123456").unwrap();
+
+        let options = ApplyOptions {
+            check: true,
+            ..Default::default()
+        };
+        let result = run(&loader, "test", &options);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("1 target(s) out of date: app")
+        );
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 123456"
+        );
+    }
+
+    #[test]
+    fn test_apply_exposes_previous_palette() {
+        let (_temp, loader, output_path) = setup_test_env();
+        write_palette(
+            &loader,
+            "other",
+            &sample_palette("Other").replace("000000", "282828"),
+        );
+        write_template(
+            &loader,
+            "colors.conf",
+            "{{ prev_name | default(value=\"none\") }} {{ base00 }}",
+        );
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "none 000000");
+
+        run(&loader, "other", &ApplyOptions::default()).unwrap();
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "Test 282828");
+    }
+
+    #[test]
+    fn test_apply_no_reload_writes_output_without_running_reload_cmd() {
+        let (temp, loader, output_path) = setup_test_env();
+        let marker = temp.path().join("reloaded");
+        let mut config = loader.load().unwrap();
+        config.targets[0].reload_cmd = format!("touch {}", marker.display());
+        loader.save(&config).unwrap();
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                no_reload: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 000000"
+        );
+        assert!(!marker.exists());
+
+        // The output is unchanged now, so only a forced apply reloads.
+        let force = ApplyOptions {
+            force: true,
+            ..Default::default()
+        };
+        run(&loader, "test", &force).unwrap();
+        assert!(marker.exists());
+    }
+
+    #[test]
+    fn test_apply_skips_unchanged_targets() {
+        let (temp, loader, output_path) = setup_test_env();
+        let log = temp.path().join("reload.log");
+        let mut config = loader.load().unwrap();
+        config.targets[0].reload_cmd = format!("echo reloaded >> {}", log.display());
+        loader.save(&config).unwrap();
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        let written = fs::metadata(&output_path).unwrap().modified().unwrap();
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert_eq!(fs::read_to_string(&log).unwrap(), "reloaded\n");
+        assert_eq!(
+            fs::metadata(&output_path).unwrap().modified().unwrap(),
+            written
+        );
+
+        fs::write(
+            loader.config_dir().join("templates").join("colors.conf"),
+            "fg {{ base05 }}",
+        )
+        .unwrap();
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "fg 555555");
+        assert_eq!(fs::read_to_string(&log).unwrap(), "reloaded\nreloaded\n");
+    }
+
+    #[test]
+    fn test_apply_force_rewrites_unchanged_targets() {
+        let (temp, loader, output_path) = setup_test_env();
+        let log = temp.path().join("reload.log");
+        let mut config = loader.load().unwrap();
+        config.targets[0].reload_cmd = format!("echo reloaded >> {}", log.display());
+        loader.save(&config).unwrap();
+        let force = ApplyOptions {
+            force: true,
+            ..Default::default()
+        };
+
+        run(&loader, "test", &force).unwrap();
+        fs::write(&output_path, "background 000000").unwrap();
+        let stale = fs::metadata(&output_path).unwrap().modified().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        run(&loader, "test", &force).unwrap();
+
+        assert_eq!(fs::read_to_string(&log).unwrap(), "reloaded\nreloaded\n");
+        assert_ne!(
+            fs::metadata(&output_path).unwrap().modified().unwrap(),
+            stale
+        );
+    }
+
+    #[test]
+    fn test_apply_output_dir_redirects_output_and_skips_reload() {
+        let (temp, loader, output_path) = setup_test_env();
+        let marker = temp.path().join("reloaded");
+        let mut config = loader.load().unwrap();
+        config.targets[0].reload_cmd = format!("touch {}", marker.display());
+        config.post_apply_cmd = format!("touch {}", marker.display());
+        loader.save(&config).unwrap();
+        let output_dir = temp.path().join("scratch");
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                output_dir: Some(output_dir.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output_dir.join("app.conf")).unwrap(),
+            "background 000000"
+        );
+        assert!(!output_path.exists());
+        assert!(!marker.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "previous");
+    }
+
+    #[test]
+    fn test_apply_unknown_palette() {
+        let (_temp, loader, _) = setup_test_env();
+
+        let result = run(&loader, "missing", &ApplyOptions::default());
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to load palette 'missing'")
+        );
+    }
+
+    fn setup_two_targets(temp: &TempDir, loader: &ConfigLoader) -> (PathBuf, PathBuf) {
+        let kitty = temp.path().join("out").join("kitty.conf");
+        let waybar = temp.path().join("out").join("waybar.css");
+        let mut config = loader.load().unwrap();
+        config.targets = [("kitty", &kitty), ("waybar", &waybar)]
+            .into_iter()
+            .map(|(name, output)| Target {
+                name: name.to_string(),
+                header: false,
+                ..app_target(output)
+            })
+            .collect();
+        loader.save(&config).unwrap();
+        (kitty, waybar)
+    }
+
+    /// Makes kitty run after waybar, with both logging their name on reload.
+    fn setup_ordered_targets(temp: &TempDir, loader: &ConfigLoader) -> PathBuf {
+        setup_two_targets(temp, loader);
+        let log = temp.path().join("reload.log");
+        let mut config = loader.load().unwrap();
+        for target in &mut config.targets {
+            target.reload_cmd = format!("echo {{target}} >> {}", log.display());
+        }
+        config.targets[0].after = vec!["waybar".to_string()];
+        loader.save(&config).unwrap();
+        log
+    }
+
+    #[test]
+    fn test_apply_runs_target_after_its_dependency() {
+        let (temp, loader, _) = setup_test_env();
+        let log = setup_ordered_targets(&temp, &loader);
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+
+        assert_eq!(fs::read_to_string(&log).unwrap(), "waybar\nkitty\n");
+    }
+
+    #[test]
+    fn test_apply_parallel_runs_target_after_its_dependency() {
+        let (temp, loader, _) = setup_test_env();
+        let log = setup_ordered_targets(&temp, &loader);
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                parallel: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&log).unwrap(), "waybar\nkitty\n");
+    }
+
+    #[test]
+    fn test_apply_rejects_cyclic_dependencies() {
+        let (temp, loader, _) = setup_test_env();
+        let (kitty, _) = setup_two_targets(&temp, &loader);
+        let mut config = loader.load().unwrap();
+        config.targets[0].after = vec!["waybar".to_string()];
+        config.targets[1].after = vec!["kitty".to_string()];
+        loader.save(&config).unwrap();
+
+        let result = run(&loader, "test", &ApplyOptions::default());
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Targets have cyclic 'after' dependencies: kitty, waybar"
+        );
+        assert!(!kitty.exists());
+    }
+
+    fn named(name: &str, after: &[&str]) -> Target {
+        Target {
+            name: name.to_string(),
+            after: after.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn names(targets: &[&Target]) -> Vec<String> {
+        targets.iter().map(|t| t.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_order_targets_keeps_declaration_order_as_tiebreaker() {
+        let targets = vec![
+            named("a", &[]),
+            named("b", &["d"]),
+            named("c", &[]),
+            named("d", &[]),
+        ];
+
+        let ordered = order_targets(&targets).unwrap();
+
+        assert_eq!(names(&ordered), vec!["a", "c", "d", "b"]);
+    }
+
+    #[test]
+    fn test_order_targets_rejects_unknown_and_self_dependencies() {
+        let unknown = order_targets(&[named("a", &["nope"])]).unwrap_err();
+        assert_eq!(
+            unknown.to_string(),
+            "Target 'a' runs after unknown target 'nope'"
+        );
+
+        let cycle = order_targets(&[named("a", &["a"])]).unwrap_err();
+        assert!(cycle.to_string().contains("cyclic"));
+    }
+
+    #[test]
+    fn test_parallel_batches_follow_dependencies() {
+        let targets = [
+            named("a", &[]),
+            named("b", &["a"]),
+            named("c", &[]),
+            named("d", &["b", "missing"]),
+        ];
+        let refs: Vec<&Target> = targets.iter().collect();
+
+        let batches: Vec<Vec<String>> = parallel_batches(&refs)
+            .iter()
+            .map(|batch| names(batch))
+            .collect();
+
+        assert_eq!(batches, vec![vec!["a", "c"], vec!["b"], vec!["d"]]);
+    }
+
+    #[test]
+    fn test_apply_only_processes_named_targets() {
+        let (temp, loader, _) = setup_test_env();
+        let (kitty, waybar) = setup_two_targets(&temp, &loader);
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                only: vec!["kitty".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(kitty.exists());
+        assert!(!waybar.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+        assert_eq!(loader.load().unwrap().targets.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_except_skips_named_targets() {
+        let (temp, loader, _) = setup_test_env();
+        let (kitty, waybar) = setup_two_targets(&temp, &loader);
+
+        run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                except: vec!["kitty".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(!kitty.exists());
+        assert!(waybar.exists());
+    }
+
+    #[test]
+    fn test_apply_only_and_except_conflict() {
+        let (_temp, loader, output_path) = setup_test_env();
+
+        let result = run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                only: vec!["app".to_string()],
+                except: vec!["app".to_string()],
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_err());
+        assert!(!output_path.exists());
+    }
+
+    #[test]
+    fn test_select_targets_ignores_unknown_names() {
+        let target = Target {
+            name: "kitty".to_string(),
+            header: false,
+            ..Default::default()
+        };
+
+        let selected = select_targets(&[&target], &["missing".to_string()], &[]);
+
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn test_apply_records_previous_palette() {
+        let (_temp, loader, _) = setup_test_env();
+
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        assert_eq!(loader.load().unwrap().previous_palette, "previous");
+
+        // Reapplying the active palette keeps the recorded previous one.
+        run(&loader, "test", &ApplyOptions::default()).unwrap();
+        assert_eq!(loader.load().unwrap().previous_palette, "previous");
+    }
+
+    #[test]
+    fn test_apply_resolves_alias_to_palette() {
+        let (_temp, loader, output_path) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config
+            .aliases
+            .insert("mytheme".to_string(), "test".to_string());
+        loader.save(&config).unwrap();
+
+        run(&loader, "mytheme", &ApplyOptions::default()).unwrap();
+
+        assert!(output_path.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+    }
+
+    #[test]
+    fn test_apply_fails_when_a_target_fails() {
+        let (temp, loader, output_path) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config.targets.push(Target {
+            name: "broken".to_string(),
+            template: "missing.conf".to_string(),
+            header: false,
+            ..app_target(&temp.path().join("out").join("broken.conf"))
+        });
+        loader.save(&config).unwrap();
+
+        let result = run(&loader, "test", &ApplyOptions::default());
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "1 target(s) failed to apply"
+        );
+        // The other target is still written and the palette still recorded.
+        assert!(output_path.exists());
+        assert_eq!(loader.load().unwrap().active_palette, "test");
+    }
+
+    #[test]
+    fn test_apply_parallel_fails_when_a_target_fails() {
+        let (_temp, loader, _) = setup_test_env();
+        let mut config = loader.load().unwrap();
+        config.targets[0].template = "missing.conf".to_string();
+        loader.save(&config).unwrap();
+
+        let result = run(
+            &loader,
+            "test",
+            &ApplyOptions {
+                parallel: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_succeeds_when_every_target_succeeds() {
+        let (_temp, loader, _) = setup_test_env();
+
+        assert!(run(&loader, "test", &ApplyOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_apply_palette_file_outside_palettes_dir() {
+        let (temp, loader, output_path) = setup_test_env();
+        let palette_file = temp.path().join("downloads").join("oneoff.json");
+        fs::create_dir_all(palette_file.parent().unwrap()).unwrap();
+        fs::write(
+            &palette_file,
+            sample_palette("Test").replace("000000", "123456"),
+        )
+        .unwrap();
+
+        run(
+            &loader,
+            "",
+            &ApplyOptions {
+                palette_file: Some(palette_file),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 123456"
+        );
+        assert_eq!(loader.load().unwrap().active_palette, "previous");
+    }
+
+    #[test]
+    fn test_apply_piped_palette() {
+        let (_temp, loader, output_path) = setup_test_env();
+        let input = sample_palette("Test").replace("000000", "654321");
+
+        let palette = read_palette(input.as_bytes(), Some("piped")).unwrap();
+        assert_eq!(palette.name, "piped");
+
+        run(
+            &loader,
+            STDIN_PALETTE,
+            &ApplyOptions {
+                piped_palette: Some(palette),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "background 654321"
+        );
+        assert_eq!(loader.load().unwrap().active_palette, "previous");
+        assert!(
+            !loader
+                .config_dir()
+                .join("palettes")
+                .join("piped.json")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_read_palette_defaults_name_and_rejects_bad_input() {
+        let unnamed = format!(r#"{{"base_16": {}}}"#, SAMPLE_BASE16);
+
+        assert_eq!(
+            read_palette(unnamed.as_bytes(), None).unwrap().name,
+            "stdin"
+        );
+        assert_eq!(
+            read_palette(sample_palette("Test").as_bytes(), None)
+                .unwrap()
+                .name,
+            "Test"
+        );
+
+        let err = read_palette(&b"{ not json"[..], None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Failed to parse palette JSON from stdin")
+        );
+
+        let bad_color = sample_palette("Test").replace("000000", "zzzzzz");
+        assert!(read_palette(bad_color.as_bytes(), None).is_err());
+    }
+
+    #[test]
+    fn test_apply_missing_palette_file() {
+        let (temp, loader, _) = setup_test_env();
+
+        let result = run(
+            &loader,
+            "",
+            &ApplyOptions {
+                palette_file: Some(temp.path().join("missing.json")),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to load palette file")
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::target::processor::{TargetProcessor, resolve_cache_dir};
+
+pub fn clear(dry_run: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run_clear(&config_loader, dry_run)
+}
+
+/// Removes the files themer wrote to the cache directory: include and
+/// symlink outputs of the configured targets and the cached wallpaper.
+/// Anything else in `cache_dir` is left alone, since it may be a shared
+/// directory such as `$XDG_RUNTIME_DIR`. With `dry_run`, only lists what
+/// would be removed.
+fn run_clear(config_loader: &ConfigLoader, dry_run: bool) -> Result<()> {
+    let config = config_loader.load()?;
+    let cache_dir = resolve_cache_dir(&config.cache_dir)?;
+
+    let files: Vec<PathBuf> = TargetProcessor::new(config_loader.config_dir())
+        .with_cache_dir(&config.cache_dir)
+        .cached_files(&config.targets)?
+        .into_iter()
+        .filter(|path| path.symlink_metadata().is_ok())
+        .collect();
+
+    if files.is_empty() {
+        output::info(&format!("Cache is already empty: {}", cache_dir.display()));
+        return Ok(());
+    }
+
+    if dry_run {
+        output::header(&format!("Would remove from {}", cache_dir.display()));
+        for file in &files {
+            output::item(Some("dry-run"), &file.display().to_string(), None);
+        }
+        return Ok(());
+    }
+
+    for file in &files {
+        fs::remove_file(file)
+            .with_context(|| format!("Failed to remove cached file: {}", file.display()))?;
+    }
+
+    output::success(&format!(
+        "Removed {} cached file(s) from {}",
+        files.len(),
+        cache_dir.display()
+    ));
+    output::info("Symlink targets point into the cache; reapply to restore them");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::setup_config_dir;
+    use crate::config::models::{Config, Mode, Target};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader, PathBuf) {
+        let (temp_dir, loader) = setup_config_dir();
+        let cache_dir = temp_dir.path().join("cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join("waybar.css"), "cached").unwrap();
+        fs::write(cache_dir.join("wallpaper"), "image").unwrap();
+
+        let config = Config {
+            cache_dir: cache_dir.display().to_string(),
+            targets: vec![Target {
+                name: "waybar".to_string(),
+                template: "waybar.css".to_string(),
+                mode: Mode::Include,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+        (temp_dir, loader, cache_dir)
+    }
+
+    fn cache_entries(cache_dir: &Path) -> Vec<PathBuf> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(cache_dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn test_clear_removes_cached_outputs() {
+        let (_temp, loader, cache_dir) = setup_test_env();
+
+        run_clear(&loader, false).unwrap();
+
+        assert!(cache_entries(&cache_dir).is_empty());
+    }
+
+    #[test]
+    fn test_clear_keeps_files_themer_did_not_write() {
+        let (_temp, loader, cache_dir) = setup_test_env();
+        fs::write(cache_dir.join("wayland-0"), "socket").unwrap();
+        fs::create_dir(cache_dir.join("dbus")).unwrap();
+
+        run_clear(&loader, false).unwrap();
+
+        assert_eq!(
+            cache_entries(&cache_dir),
+            vec![cache_dir.join("dbus"), cache_dir.join("wayland-0")]
+        );
+    }
+
+    #[test]
+    fn test_clear_dry_run_keeps_files() {
+        let (_temp, loader, cache_dir) = setup_test_env();
+
+        run_clear(&loader, true).unwrap();
+
+        assert_eq!(
+            cache_entries(&cache_dir),
+            vec![cache_dir.join("wallpaper"), cache_dir.join("waybar.css")]
+        );
+    }
+
+    #[test]
+    fn test_clear_missing_cache_dir_is_ok() {
+        let (_temp, loader, cache_dir) = setup_test_env();
+        fs::remove_dir_all(&cache_dir).unwrap();
+
+        assert!(run_clear(&loader, false).is_ok());
+    }
+}
@@ -0,0 +1,280 @@
+use anyhow::Result;
+use std::collections::HashSet;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::Mode;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+use crate::target::processor::{parse_mode_bits, placeholder_problems};
+
+pub fn execute() -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    output::header("Checking configuration...");
+
+    let problems = run(&config_loader)?;
+
+    if problems.is_empty() {
+        output::success("No problems found");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        output::error(problem);
+    }
+    anyhow::bail!("{} problem(s) found in config.toml", problems.len())
+}
+
+/// Checks config.toml for mistakes that only show up at apply time and
+/// returns a description of each one. A config that fails to parse (e.g. an
+/// unknown mode) is reported as a single problem.
+fn run(config_loader: &ConfigLoader) -> Result<Vec<String>> {
+    let config = match config_loader.load() {
+        Ok(config) => config,
+        Err(e) => return Ok(vec![format!("{:#}", e)]),
+    };
+
+    let templates_dir = config_loader.config_dir().join("templates");
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+
+    for target in &config.targets {
+        if !seen.insert(target.name.as_str()) {
+            problems.push(format!("Duplicate target name '{}'", target.name));
+        }
+
+        if target.template_files().next().is_none() {
+            problems.push(format!("Target '{}' has no template", target.name));
+        }
+
+        for template in target.template_files() {
+            if !templates_dir.join(template).is_file() {
+                problems.push(format!(
+                    "Target '{}' uses missing template '{}'",
+                    target.name, template
+                ));
+            }
+        }
+
+        if target.mode != Mode::Include && target.output.is_empty() {
+            problems.push(format!(
+                "Target '{}' with mode '{}' has an empty output",
+                target.name, target.mode
+            ));
+        }
+
+        problems.extend(placeholder_problems(target));
+
+        if let Some(Err(e)) = target.mode_bits.as_deref().map(parse_mode_bits) {
+            problems.push(format!("Target '{}': {}", target.name, e));
+        }
+    }
+
+    let palette_loader = match PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)
+    {
+        Ok(palette_loader) => palette_loader,
+        Err(e) => {
+            problems.push(format!("palette_dirs: {:#}", e));
+            return Ok(problems);
+        }
+    };
+    if !config.active_palette.is_empty() && !palette_loader.exists(&config.active_palette) {
+        problems.push(format!(
+            "active_palette '{}' does not exist",
+            config.active_palette
+        ));
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_palette, write_template};
+    use crate::config::models::{Config, Target};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_template(&loader, "kitty.conf", "");
+        write_palette(&loader, "nord", r#"{"name": "Nord"}"#);
+        (temp_dir, loader)
+    }
+
+    fn target(name: &str, template: &str, mode: Mode, output: &str) -> Target {
+        Target {
+            name: name.to_string(),
+            template: template.to_string(),
+            output: output.to_string(),
+            mode,
+            ..Default::default()
+        }
+    }
+
+    fn save_config(loader: &ConfigLoader, active_palette: &str, targets: Vec<Target>) {
+        let config = Config {
+            active_palette: active_palette.to_string(),
+            targets,
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+    }
+
+    #[test]
+    fn test_check_valid_config() {
+        let (_temp, loader) = setup_test_env();
+        save_config(
+            &loader,
+            "nord",
+            vec![target("kitty", "kitty.conf", Mode::Include, "")],
+        );
+
+        assert!(run(&loader).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_check_missing_template() {
+        let (_temp, loader) = setup_test_env();
+        save_config(
+            &loader,
+            "",
+            vec![target("waybar", "waybar.css", Mode::Include, "")],
+        );
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(
+            problems,
+            vec!["Target 'waybar' uses missing template 'waybar.css'"]
+        );
+    }
+
+    #[test]
+    fn test_check_missing_one_of_several_templates() {
+        let (_temp, loader) = setup_test_env();
+        let mut alacritty = target("alacritty", "kitty.conf", Mode::Include, "");
+        alacritty.templates = vec!["font.toml".to_string()];
+        save_config(&loader, "", vec![alacritty]);
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(
+            problems,
+            vec!["Target 'alacritty' uses missing template 'font.toml'"]
+        );
+    }
+
+    #[test]
+    fn test_check_target_without_template() {
+        let (_temp, loader) = setup_test_env();
+        save_config(&loader, "", vec![target("empty", "", Mode::Include, "")]);
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(problems, vec!["Target 'empty' has no template"]);
+    }
+
+    #[test]
+    fn test_check_duplicate_target_names() {
+        let (_temp, loader) = setup_test_env();
+        save_config(
+            &loader,
+            "",
+            vec![
+                target("kitty", "kitty.conf", Mode::Include, ""),
+                target("kitty", "kitty.conf", Mode::Include, ""),
+            ],
+        );
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(problems, vec!["Duplicate target name 'kitty'"]);
+    }
+
+    #[test]
+    fn test_check_replace_target_without_output() {
+        let (_temp, loader) = setup_test_env();
+        save_config(
+            &loader,
+            "",
+            vec![target("kitty", "kitty.conf", Mode::Replace, "")],
+        );
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(
+            problems,
+            vec!["Target 'kitty' with mode 'replace' has an empty output"]
+        );
+    }
+
+    #[test]
+    fn test_check_unknown_mode() {
+        let (_temp, loader) = setup_test_env();
+        fs::write(
+            loader.config_dir().join("config.toml"),
+            r#"
+active_palette = ""
+
+[[targets]]
+name = "kitty"
+template = "kitty.conf"
+output = ""
+mode = "append"
+reload_cmd = ""
+"#,
+        )
+        .unwrap();
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("Failed to parse config.toml"));
+        assert!(problems[0].contains("unknown variant `append`"));
+    }
+
+    #[test]
+    fn test_check_missing_active_palette() {
+        let (_temp, loader) = setup_test_env();
+        save_config(&loader, "deleted", vec![]);
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(problems, vec!["active_palette 'deleted' does not exist"]);
+    }
+
+    #[test]
+    fn test_check_invalid_mode_bits() {
+        let (_temp, loader) = setup_test_env();
+        let mut kitty = target("kitty", "kitty.conf", Mode::Include, "");
+        kitty.mode_bits = Some("0789".to_string());
+        save_config(&loader, "", vec![kitty]);
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(
+            problems,
+            vec![
+                "Target 'kitty': Invalid mode_bits '0789', expected octal permissions like \"0755\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_unknown_reload_placeholder() {
+        let (_temp, loader) = setup_test_env();
+        let mut kitty = target("kitty", "kitty.conf", Mode::Include, "");
+        kitty.reload_cmd = "notify-send {them}".to_string();
+        save_config(&loader, "", vec![kitty]);
+
+        let problems = run(&loader).unwrap();
+
+        assert_eq!(
+            problems,
+            vec!["Target 'kitty' reload_cmd has unknown placeholder '{them}'"]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use clap_complete::Shell;
+
+use crate::config::loader::ConfigLoader;
+use crate::palette::loader::PaletteLoader;
+
+/// Hidden subcommand the completion scripts call to list palette names.
+pub const COMPLETE_PALETTES: &str = "__complete-palettes";
+
+/// Subcommands taking a palette as their positional argument, and the ones
+/// taking it through `--palette`.
+const PALETTE_ARGUMENT_COMMANDS: [&str; 2] = ["apply", "export"];
+const PALETTE_OPTION_COMMANDS: [&str; 5] = ["preview", "render", "diff", "watch", "edit"];
+
+/// Prints the completion script for `shell`. Bash and fish scripts also
+/// complete palette names through [`COMPLETE_PALETTES`].
+pub fn execute(shell: Shell, command: &mut clap::Command) -> Result<()> {
+    print!("{}", script(shell, command));
+    Ok(())
+}
+
+fn script(shell: Shell, command: &mut clap::Command) -> String {
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, command, &name, &mut script);
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+
+    match shell {
+        Shell::Bash => script.push_str(&bash_palettes(&name)),
+        Shell::Fish => script.push_str(&fish_palettes(&name)),
+        _ => {}
+    }
+
+    script
+}
+
+/// Wraps the generated `_<name>` function so palette arguments complete to
+/// palette names instead of nothing.
+fn bash_palettes(name: &str) -> String {
+    format!(
+        r#"
+_{name}_palettes() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    if [[ "$prev" == "--palette" ]] || {{ [[ $COMP_CWORD -eq 2 && "$cur" != -* ]] && [[ " {positional} " == *" ${{COMP_WORDS[1]}} "* ]]; }}; then
+        COMPREPLY=($(compgen -W "$({name} {complete} 2>/dev/null)" -- "$cur"))
+        return 0
+    fi
+    _{name} "$@"
+}}
+complete -F _{name}_palettes -o bashdefault -o default {name}
+"#,
+        name = name,
+        positional = PALETTE_ARGUMENT_COMMANDS.join(" "),
+        complete = COMPLETE_PALETTES,
+    )
+}
+
+fn fish_palettes(name: &str) -> String {
+    let palettes = format!("({} {} 2>/dev/null)", name, COMPLETE_PALETTES);
+    format!(
+        "complete -c {name} -n \"__fish_seen_subcommand_from {positional}\" -f -a \"{palettes}\"\n\
+         complete -c {name} -n \"__fish_seen_subcommand_from {option}\" -l palette -x -a \"{palettes}\"\n",
+        name = name,
+        positional = PALETTE_ARGUMENT_COMMANDS.join(" "),
+        option = PALETTE_OPTION_COMMANDS.join(" "),
+        palettes = palettes,
+    )
+}
+
+/// Prints every palette name and alias, one per line, for the completion
+/// scripts. Prints nothing rather than failing, since errors would end up
+/// in the user's prompt.
+pub fn complete_palettes() -> Result<()> {
+    let names = ConfigLoader::new()
+        .and_then(|config_loader| palette_names(&config_loader))
+        .unwrap_or_default();
+
+    for name in names {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
+fn palette_names(config_loader: &ConfigLoader) -> Result<Vec<String>> {
+    let config = config_loader.load()?;
+    let palettes = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?
+        .list_all()?;
+
+    let mut names: Vec<String> = palettes
+        .into_iter()
+        .map(|info| info.filename)
+        .chain(config.aliases.into_keys())
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_sample_palette};
+    use crate::config::models::Config;
+
+    fn test_command() -> clap::Command {
+        clap::Command::new("themer")
+            .subcommand(clap::Command::new("apply").arg(clap::Arg::new("palette")))
+            .subcommand(clap::Command::new("validate"))
+    }
+
+    #[test]
+    fn test_bash_script_completes_subcommands_and_palettes() {
+        let script = script(Shell::Bash, &mut test_command());
+
+        assert!(script.contains("apply"));
+        assert!(script.contains("validate"));
+        assert!(script.contains("themer __complete-palettes"));
+        assert!(script.contains("complete -F _themer_palettes"));
+    }
+
+    #[test]
+    fn test_zsh_script_has_no_palette_hook() {
+        let script = script(Shell::Zsh, &mut test_command());
+
+        assert!(script.contains("#compdef themer"));
+        assert!(!script.contains(COMPLETE_PALETTES));
+    }
+
+    #[test]
+    fn test_palette_names_include_aliases() {
+        let (_temp, loader) = setup_config_dir();
+        for name in ["nord", "gruvbox"] {
+            write_sample_palette(&loader, name);
+        }
+        let mut config = Config::default();
+        config
+            .aliases
+            .insert("dark".to_string(), "nord".to_string());
+        loader.save(&config).unwrap();
+
+        assert_eq!(
+            palette_names(&loader).unwrap(),
+            vec!["dark", "gruvbox", "nord"]
+        );
+    }
+}
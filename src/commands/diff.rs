@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use std::io::ErrorKind;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::Mode;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+use crate::target::processor::{TargetProcessor, template_parts};
+use crate::template::engine::TemplateEngine;
+
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+enum TargetDiff {
+    Unchanged,
+    Changed(Vec<(ChangeTag, String)>),
+}
+
+pub fn execute(palette_name: &str) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, palette_name)?;
+    Ok(())
+}
+
+/// Prints the pending changes for every enabled replace-mode target and
+/// returns how many of them would change. A target that fails to render is
+/// reported and skipped; the run still fails once every target was shown.
+fn run(config_loader: &ConfigLoader, palette_name: &str) -> Result<usize> {
+    output::header(&format!("Pending changes for palette: {}", palette_name));
+
+    let config = config_loader.load()?;
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+    let palette = palette_loader
+        .load(palette_name)
+        .with_context(|| format!("Failed to load palette '{}'", palette_name))?;
+
+    let engine = TemplateEngine::new();
+    let context = engine.create_target_context(&palette, palette_name, &config, &palette_loader)?;
+    let mut processor = TargetProcessor::new(config_loader.config_dir());
+    let mut changed = 0;
+    let mut failed = 0;
+
+    for target in &config.targets {
+        if !target.enabled {
+            output::item(Some("skip"), &target.name, Some("disabled"));
+            continue;
+        }
+        if target.mode != Mode::Replace {
+            output::item(
+                Some("skip"),
+                &target.name,
+                Some(&format!("{} mode", target.mode)),
+            );
+            continue;
+        }
+
+        let mut target_changed = false;
+        for part in template_parts(target) {
+            let (rendered, output_path) = match processor.render_output(&part, &context) {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    output::item(Some("✗"), &part.name, Some(&format!("{:#}", e)));
+                    failed += 1;
+                    continue;
+                }
+            };
+            // A missing file diffs as empty, so every rendered line shows as added.
+            let current = match fs::read_to_string(&output_path) {
+                Ok(current) => current,
+                Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("Failed to read {}", output_path.display()));
+                }
+            };
+
+            match diff_contents(&current, &rendered) {
+                TargetDiff::Unchanged => {
+                    output::item(Some("="), &part.name, Some("unchanged"));
+                }
+                TargetDiff::Changed(lines) => {
+                    target_changed = true;
+                    output::item(
+                        Some("~"),
+                        &part.name,
+                        Some(&output_path.display().to_string()),
+                    );
+                    for (tag, line) in &lines {
+                        output::diff_line(sign(*tag), line);
+                    }
+                }
+            }
+        }
+        if target_changed {
+            changed += 1;
+        }
+    }
+
+    if changed == 0 && failed == 0 {
+        output::success("No changes");
+    } else if changed > 0 {
+        output::info(&format!("{} target(s) would change", changed));
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} target(s) failed to render", failed);
+    }
+
+    Ok(changed)
+}
+
+fn diff_contents(current: &str, rendered: &str) -> TargetDiff {
+    if current == rendered {
+        return TargetDiff::Unchanged;
+    }
+
+    let diff = TextDiff::from_lines(current, rendered);
+    let lines = diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .iter_hunks()
+        .flat_map(|hunk| {
+            hunk.iter_changes()
+                .map(|change| {
+                    (
+                        change.tag(),
+                        change.to_string_lossy().trim_end().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    TargetDiff::Changed(lines)
+}
+
+fn sign(tag: ChangeTag) -> char {
+    match tag {
+        ChangeTag::Insert => '+',
+        ChangeTag::Delete => '-',
+        ChangeTag::Equal => ' ',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{
+        app_target, sample_palette, setup_config_dir, write_palette, write_template,
+    };
+    use crate::config::models::{Config, Target};
+    use tempfile::TempDir;
+
+    fn setup_test_env(current_output: &str) -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_palette(&loader, "test", &sample_palette("Test"));
+        write_template(
+            &loader,
+            "colors.conf",
+            "# colors\nbackground {{ base00 }}\nforeground {{ base05 }}\n",
+        );
+
+        let output_path = temp_dir.path().join("colors.conf");
+        fs::write(&output_path, current_output).unwrap();
+
+        let config = Config {
+            active_palette: String::new(),
+            targets: vec![app_target(&output_path)],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_diff_contents_identical() {
+        assert_eq!(diff_contents("a\nb\n", "a\nb\n"), TargetDiff::Unchanged);
+    }
+
+    #[test]
+    fn test_diff_contents_changed_line() {
+        let TargetDiff::Changed(lines) = diff_contents("a\nb\nc\n", "a\nx\nc\n") else {
+            panic!("Expected a diff");
+        };
+
+        assert!(lines.contains(&(ChangeTag::Delete, "b".to_string())));
+        assert!(lines.contains(&(ChangeTag::Insert, "x".to_string())));
+    }
+
+    #[test]
+    fn test_diff_reports_changed_target() {
+        let (_temp, loader) = setup_test_env("# colors\nbackground 282828\nforeground 555555\n");
+
+        assert_eq!(run(&loader, "test").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_diff_skips_disabled_target() {
+        let (_temp, loader) = setup_test_env("# colors\nbackground 282828\nforeground 555555\n");
+        let mut config = loader.load().unwrap();
+        config.targets[0].enabled = false;
+        loader.save(&config).unwrap();
+
+        assert_eq!(run(&loader, "test").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_diff_continues_past_render_error() {
+        let (temp_dir, loader) = setup_test_env("# colors\nbackground 282828\nforeground 555555\n");
+        write_template(&loader, "broken.conf", "{{ no_such_color }}\n");
+        let mut config = loader.load().unwrap();
+        config.targets.insert(
+            0,
+            Target {
+                name: "broken".to_string(),
+                template: "broken.conf".to_string(),
+                ..app_target(&temp_dir.path().join("broken.conf"))
+            },
+        );
+        loader.save(&config).unwrap();
+
+        let err = run(&loader, "test").unwrap_err();
+        assert_eq!(err.to_string(), "1 target(s) failed to render");
+    }
+
+    #[test]
+    fn test_diff_propagates_unreadable_output() {
+        let (temp_dir, loader) = setup_test_env("");
+        let output_path = temp_dir.path().join("colors.conf");
+        fs::remove_file(&output_path).unwrap();
+        fs::create_dir(&output_path).unwrap();
+
+        assert!(run(&loader, "test").is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_unchanged_target() {
+        // Compared against what apply writes, generated-file header included.
+        let (_temp, loader) = setup_test_env(
+            "# DO NOT EDIT — generated by themer\n# colors\nbackground 000000\nforeground 555555\n",
+        );
+
+        assert_eq!(run(&loader, "test").unwrap(), 0);
+    }
+}
@@ -0,0 +1,420 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::Config;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+use crate::target::processor::resolve_cache_dir;
+
+const REQUIRED_DIRS: [&str; 2] = ["palettes", "templates"];
+
+pub fn execute(fix: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    output::header("Running diagnostics...");
+
+    let unresolved = run(&config_loader, fix)?;
+
+    if unresolved == 0 {
+        output::success("No problems found");
+        return Ok(());
+    }
+
+    if fix {
+        anyhow::bail!("{} problem(s) could not be fixed", unresolved);
+    }
+
+    output::info("Run 'themer doctor --fix' to repair what it can");
+    anyhow::bail!("{} problem(s) found", unresolved)
+}
+
+/// Runs every check in order, repairing what it can when `fix` is set.
+/// Returns the number of problems left unresolved.
+fn run(config_loader: &ConfigLoader, fix: bool) -> Result<usize> {
+    let mut unresolved = 0;
+
+    if !check_config_dir(config_loader, fix)? {
+        unresolved += 1;
+    }
+
+    for dir in REQUIRED_DIRS {
+        if !check_dir(config_loader, dir, fix)? {
+            unresolved += 1;
+        }
+    }
+
+    let Some(mut config) = check_config(config_loader, fix)? else {
+        // Without a usable config there is no active palette or target to
+        // inspect.
+        return Ok(unresolved + 1);
+    };
+
+    if !check_active_palette(config_loader, &mut config, fix)? {
+        unresolved += 1;
+    }
+
+    if !check_cache_dir(&config) {
+        unresolved += 1;
+    }
+
+    report_counts(config_loader, &config);
+    unresolved += check_target_templates(config_loader, &config);
+
+    Ok(unresolved)
+}
+
+fn check_config_dir(config_loader: &ConfigLoader, fix: bool) -> Result<bool> {
+    let path = config_loader.config_dir();
+    let label = path.display().to_string();
+
+    if path.is_dir() {
+        output::item(Some("✓"), &label, Some("Found"));
+        return Ok(true);
+    }
+
+    if !fix {
+        output::item(Some("✗"), &label, Some("Missing"));
+        return Ok(false);
+    }
+
+    fs::create_dir_all(path)
+        .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+    output::success(&format!("Created {}", path.display()));
+    Ok(true)
+}
+
+/// Include and symlink targets render into the cache directory, so it has
+/// to resolve even when nothing has been written there yet.
+fn check_cache_dir(config: &Config) -> bool {
+    match resolve_cache_dir(&config.cache_dir).ok() {
+        Some(dir) => {
+            output::item(Some("✓"), "cache_dir", Some(&dir.display().to_string()));
+            true
+        }
+        None => {
+            output::item(Some("✗"), "cache_dir", Some("Could not be resolved"));
+            false
+        }
+    }
+}
+
+fn report_counts(config_loader: &ConfigLoader, config: &Config) {
+    let palettes = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)
+        .and_then(|loader| loader.list_all())
+        .map_or(0, |palettes| palettes.len());
+
+    output::item(None, "palettes", Some(&format!("{} found", palettes)));
+    output::item(
+        None,
+        "targets",
+        Some(&format!("{} configured", config.targets.len())),
+    );
+}
+
+/// Reports each target whose template file is missing. Returns how many are.
+fn check_target_templates(config_loader: &ConfigLoader, config: &Config) -> usize {
+    let templates_dir = config_loader.config_dir().join("templates");
+    let mut missing = 0;
+
+    for target in &config.targets {
+        for template in target.template_files() {
+            let label = format!("{} → templates/{}", target.name, template);
+            if templates_dir.join(template).is_file() {
+                output::item(Some("✓"), &label, Some("Found"));
+            } else {
+                output::item(Some("✗"), &label, Some("Missing"));
+                missing += 1;
+            }
+        }
+    }
+
+    missing
+}
+
+fn check_dir(config_loader: &ConfigLoader, dir: &str, fix: bool) -> Result<bool> {
+    let path = config_loader.config_dir().join(dir);
+    let label = format!("{}/", dir);
+
+    if path.is_dir() {
+        output::item(Some("✓"), &label, Some("Found"));
+        return Ok(true);
+    }
+
+    if !fix {
+        output::item(Some("✗"), &label, Some("Missing"));
+        return Ok(false);
+    }
+
+    fs::create_dir_all(&path)
+        .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+    output::success(&format!("Created {}", path.display()));
+    Ok(true)
+}
+
+/// Returns the loaded config, or `None` when it is missing or doesn't parse.
+/// A broken config is reported but never overwritten, even with `fix`.
+fn check_config(config_loader: &ConfigLoader, fix: bool) -> Result<Option<Config>> {
+    let config_path = config_loader.config_dir().join("config.toml");
+
+    if config_path.is_file() {
+        return Ok(match config_loader.load() {
+            Ok(config) => {
+                output::item(Some("✓"), "config.toml", Some("Found"));
+                Some(config)
+            }
+            Err(e) => {
+                output::item(Some("✗"), "config.toml", Some(&format!("{:#}", e)));
+                None
+            }
+        });
+    }
+
+    if !fix {
+        output::item(Some("✗"), "config.toml", Some("Missing"));
+        return Ok(None);
+    }
+
+    fs::create_dir_all(config_loader.config_dir()).with_context(|| {
+        format!(
+            "Failed to create directory: {}",
+            config_loader.config_dir().display()
+        )
+    })?;
+
+    let config = Config::default();
+    config_loader.save(&config)?;
+    output::success(&format!("Created {}", config_path.display()));
+    Ok(Some(config))
+}
+
+fn check_active_palette(
+    config_loader: &ConfigLoader,
+    config: &mut Config,
+    fix: bool,
+) -> Result<bool> {
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+
+    let problem = if config.active_palette.is_empty() {
+        "Not set"
+    } else if !palette_loader.exists(&config.active_palette) {
+        "Points to a missing palette"
+    } else {
+        output::item(Some("✓"), "active_palette", Some(&config.active_palette));
+        return Ok(true);
+    };
+
+    if !fix {
+        output::item(Some("✗"), "active_palette", Some(problem));
+        return Ok(false);
+    }
+
+    let Some(first) = first_available_palette(&palette_loader) else {
+        output::item(Some("✗"), "active_palette", Some("No palettes available"));
+        return Ok(false);
+    };
+
+    config.active_palette = first;
+    config_loader.save(config)?;
+    output::success(&format!(
+        "Set active_palette to '{}'",
+        config.active_palette
+    ));
+    Ok(true)
+}
+
+fn first_available_palette(palette_loader: &PaletteLoader) -> Option<String> {
+    let mut palettes = palette_loader.list_all().ok()?;
+    palettes.retain(|p| p.name.is_some());
+    palettes.sort_by(|a, b| a.filename.cmp(&b.filename));
+    palettes.into_iter().next().map(|p| p.filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_config_loader;
+    use crate::config::models::Target;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = temp_config_loader();
+        fs::create_dir_all(loader.config_dir()).unwrap();
+        (temp_dir, loader)
+    }
+
+    fn write_config(loader: &ConfigLoader, active_palette: &str) {
+        let config = Config {
+            active_palette: active_palette.to_string(),
+            targets: vec![],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+    }
+
+    fn write_valid_setup(loader: &ConfigLoader) {
+        let palettes_dir = loader.config_dir().join("palettes");
+        fs::create_dir_all(&palettes_dir).unwrap();
+        fs::create_dir_all(loader.config_dir().join("templates")).unwrap();
+        fs::write(palettes_dir.join("nord.json"), r#"{"name": "Nord"}"#).unwrap();
+        write_config(loader, "nord");
+    }
+
+    #[test]
+    fn test_doctor_reports_missing_dirs_without_fix() {
+        let (_temp, loader) = setup_test_env();
+        write_config(&loader, "");
+
+        let unresolved = run(&loader, false).unwrap();
+
+        assert_eq!(unresolved, 3);
+        assert!(!loader.config_dir().join("palettes").exists());
+        assert!(!loader.config_dir().join("templates").exists());
+    }
+
+    #[test]
+    fn test_doctor_fix_creates_missing_dirs() {
+        let (_temp, loader) = setup_test_env();
+        write_config(&loader, "");
+
+        run(&loader, true).unwrap();
+
+        assert!(loader.config_dir().join("palettes").is_dir());
+        assert!(loader.config_dir().join("templates").is_dir());
+    }
+
+    #[test]
+    fn test_doctor_fix_creates_default_config() {
+        let (_temp, loader) = setup_test_env();
+
+        run(&loader, true).unwrap();
+
+        let config = loader.load().unwrap();
+        assert!(config.targets.is_empty());
+    }
+
+    #[test]
+    fn test_doctor_reports_unparsable_config() {
+        let (_temp, loader) = setup_test_env();
+        let config_path = loader.config_dir().join("config.toml");
+        fs::write(&config_path, "active_palette = ").unwrap();
+
+        // Both subdirectories and the config, which --fix leaves alone.
+        assert_eq!(run(&loader, false).unwrap(), 3);
+        assert_eq!(run(&loader, true).unwrap(), 1);
+        assert_eq!(
+            fs::read_to_string(&config_path).unwrap(),
+            "active_palette = "
+        );
+    }
+
+    #[test]
+    fn test_doctor_fix_resets_dangling_active_palette() {
+        let (_temp, loader) = setup_test_env();
+        let palettes_dir = loader.config_dir().join("palettes");
+        fs::create_dir_all(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("nord.json"), r#"{"name": "Nord"}"#).unwrap();
+        fs::write(palettes_dir.join("gruvbox.json"), r#"{"name": "Gruvbox"}"#).unwrap();
+        write_config(&loader, "deleted-palette");
+
+        let unresolved = run(&loader, true).unwrap();
+
+        assert_eq!(unresolved, 0);
+        assert_eq!(loader.load().unwrap().active_palette, "gruvbox");
+    }
+
+    #[test]
+    fn test_doctor_leaves_aliased_active_palette() {
+        let (_temp, loader) = setup_test_env();
+        let palettes_dir = loader.config_dir().join("palettes");
+        fs::create_dir_all(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("gruvbox.json"), r#"{"name": "Gruvbox"}"#).unwrap();
+        fs::write(palettes_dir.join("nord.json"), r#"{"name": "Nord"}"#).unwrap();
+        let mut config = Config {
+            active_palette: "mytheme".to_string(),
+            ..Default::default()
+        };
+        config
+            .aliases
+            .insert("mytheme".to_string(), "nord".to_string());
+        loader.save(&config).unwrap();
+
+        assert!(check_active_palette(&loader, &mut loader.load().unwrap(), false).unwrap());
+        run(&loader, true).unwrap();
+
+        assert_eq!(loader.load().unwrap().active_palette, "mytheme");
+    }
+
+    #[test]
+    fn test_doctor_leaves_valid_active_palette() {
+        let (_temp, loader) = setup_test_env();
+        let palettes_dir = loader.config_dir().join("palettes");
+        fs::create_dir_all(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("nord.json"), r#"{"name": "Nord"}"#).unwrap();
+        write_config(&loader, "nord");
+
+        run(&loader, true).unwrap();
+
+        assert_eq!(loader.load().unwrap().active_palette, "nord");
+    }
+
+    #[test]
+    fn test_doctor_flags_missing_templates_dir() {
+        let (_temp, loader) = setup_test_env();
+        write_valid_setup(&loader);
+        fs::remove_dir(loader.config_dir().join("templates")).unwrap();
+
+        let unresolved = run(&loader, false).unwrap();
+
+        assert_eq!(unresolved, 1);
+    }
+
+    #[test]
+    fn test_doctor_flags_missing_config_dir() {
+        let (_temp, loader) = temp_config_loader();
+
+        // Config dir, both subdirectories and config.toml.
+        assert_eq!(run(&loader, false).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_doctor_flags_missing_target_template() {
+        let (_temp, loader) = setup_test_env();
+        write_valid_setup(&loader);
+        let mut config = loader.load().unwrap();
+        config.targets = vec![Target {
+            name: "kitty".to_string(),
+            template: "kitty.conf".to_string(),
+            ..Default::default()
+        }];
+        loader.save(&config).unwrap();
+
+        assert_eq!(run(&loader, false).unwrap(), 1);
+
+        fs::write(loader.config_dir().join("templates").join("kitty.conf"), "").unwrap();
+        assert_eq!(run(&loader, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_check_cache_dir_undefined_var() {
+        let config = Config {
+            cache_dir: "$THEMER_TEST_UNDEFINED_VAR/themer".to_string(),
+            ..Default::default()
+        };
+
+        assert!(!check_cache_dir(&config));
+    }
+
+    #[test]
+    fn test_check_cache_dir_custom() {
+        let config = Config {
+            cache_dir: "~/themer-cache".to_string(),
+            ..Default::default()
+        };
+
+        assert!(check_cache_dir(&config));
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+pub fn execute(palette_name: Option<&str>) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    let editor = env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    run(&config_loader, palette_name, &editor)
+}
+
+fn run(config_loader: &ConfigLoader, palette_name: Option<&str>, editor: &str) -> Result<()> {
+    let path = resolve_path(config_loader, palette_name)?;
+
+    open_in_editor(editor, &path)?;
+
+    match palette_name {
+        Some(name) => match palette_loader(config_loader)?.load(name) {
+            Ok(_) => output::success(&format!("Palette '{}' is valid", name)),
+            Err(e) => output::warning(&format!("Palette '{}' is now invalid: {:#}", name, e)),
+        },
+        None => {
+            if let Err(e) = config_loader.load() {
+                output::warning(&format!("config.toml is now invalid: {:#}", e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_path(config_loader: &ConfigLoader, palette_name: Option<&str>) -> Result<PathBuf> {
+    let Some(name) = palette_name else {
+        return Ok(config_loader.config_dir().join("config.toml"));
+    };
+
+    let palette_loader = palette_loader(config_loader)?;
+    if !palette_loader.exists(name) {
+        anyhow::bail!("Palette '{}' not found", name);
+    }
+
+    Ok(palette_loader.palette_path(name))
+}
+
+fn palette_loader(config_loader: &ConfigLoader) -> Result<PaletteLoader> {
+    let config = config_loader.load()?;
+    PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)
+}
+
+/// Runs the editor through the shell so values like `code --wait` work, and
+/// waits for it to exit.
+fn open_in_editor(editor: &str, path: &Path) -> Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_palette};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader, String) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_palette(&loader, "nord", r#"{"name": "Nord"}"#);
+
+        // A fake editor that records the path it was asked to open.
+        let record = temp_dir.path().join("opened");
+        let editor = format!("echo >{}", record.display());
+
+        (temp_dir, loader, editor)
+    }
+
+    fn opened_path(temp: &TempDir) -> String {
+        fs::read_to_string(temp.path().join("opened"))
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn test_edit_opens_config_by_default() {
+        let (temp, loader, editor) = setup_test_env();
+
+        run(&loader, None, &editor).unwrap();
+
+        assert_eq!(
+            opened_path(&temp),
+            loader
+                .config_dir()
+                .join("config.toml")
+                .display()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_edit_opens_palette_file() {
+        let (temp, loader, editor) = setup_test_env();
+
+        run(&loader, Some("nord"), &editor).unwrap();
+
+        assert_eq!(
+            opened_path(&temp),
+            loader
+                .config_dir()
+                .join("palettes")
+                .join("nord.json")
+                .display()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_edit_unknown_palette() {
+        let (_temp, loader, editor) = setup_test_env();
+
+        let result = run(&loader, Some("missing"), &editor);
+
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_edit_failing_editor() {
+        let (_temp, loader, _) = setup_test_env();
+
+        let result = run(&loader, None, "false");
+
+        assert!(result.unwrap_err().to_string().contains("exited with"));
+    }
+}
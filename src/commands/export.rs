@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde::ser::SerializeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+use crate::palette::models::Palette;
+
+/// Base16 slot (index into `Base16::colors()`) for each of the 16 terminal
+/// colors, following the base16 shell/terminal convention. The bright
+/// variants reuse the normal accents.
+const TERMINAL_COLORS: [usize; 16] = [
+    0x00, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x05, 0x03, 0x08, 0x0B, 0x0A, 0x0D, 0x0E, 0x0C, 0x07,
+];
+
+pub fn execute(palette_name: &str, format: &str, out: Option<&str>) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    let exported = run(&config_loader, palette_name, format)?;
+
+    match out {
+        Some(path) => {
+            let path = shellexpand::tilde(path).into_owned();
+            write_export(Path::new(&path), &exported)?;
+            output::success(&format!("Exported '{}' to {}", palette_name, path));
+        }
+        None => print!("{}", exported),
+    }
+
+    Ok(())
+}
+
+fn run(config_loader: &ConfigLoader, palette_name: &str, format: &str) -> Result<String> {
+    let config = config_loader.load()?;
+    let palette = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?
+        .load(palette_name)
+        .with_context(|| format!("Failed to load palette '{}'", palette_name))?;
+
+    export(&palette, format)
+}
+
+fn export(palette: &Palette, format: &str) -> Result<String> {
+    match format {
+        "base16-yaml" => export_base16_yaml(palette),
+        "pywal-json" => export_pywal_json(palette),
+        "themer-json" => {
+            let json = serde_json::to_string_pretty(palette)?;
+            Ok(format!("{}\n", json))
+        }
+        unknown => anyhow::bail!(
+            "Unknown format '{}', expected 'base16-yaml', 'pywal-json' or 'themer-json'",
+            unknown
+        ),
+    }
+}
+
+fn export_base16_yaml(palette: &Palette) -> Result<String> {
+    let mut scheme = serde_yaml::Mapping::new();
+    scheme.insert("scheme".into(), palette.name.as_str().into());
+
+    for (key, color) in palette.base16()?.named_colors() {
+        scheme.insert(key.into(), color.into());
+    }
+
+    Ok(serde_yaml::to_string(&scheme)?)
+}
+
+#[derive(Serialize)]
+struct PywalTheme<'a> {
+    wallpaper: &'a str,
+    alpha: &'a str,
+    special: PywalSpecial,
+    colors: PywalColors,
+}
+
+#[derive(Serialize)]
+struct PywalSpecial {
+    background: String,
+    foreground: String,
+    cursor: String,
+}
+
+/// Serializes as `{"color0": ..., "color15": ...}` in numeric order.
+struct PywalColors(Vec<String>);
+
+impl Serialize for PywalColors {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (index, color) in self.0.iter().enumerate() {
+            map.serialize_entry(&format!("color{}", index), color)?;
+        }
+        map.end()
+    }
+}
+
+fn export_pywal_json(palette: &Palette) -> Result<String> {
+    let colors: Vec<String> = palette
+        .base16()?
+        .colors()
+        .map(|color| format!("#{}", color))
+        .collect();
+
+    let theme = PywalTheme {
+        wallpaper: "",
+        alpha: "100",
+        special: PywalSpecial {
+            background: colors[0x00].clone(),
+            foreground: colors[0x05].clone(),
+            cursor: colors[0x05].clone(),
+        },
+        colors: PywalColors(
+            TERMINAL_COLORS
+                .iter()
+                .map(|&slot| colors[slot].clone())
+                .collect(),
+        ),
+    };
+
+    let json = serde_json::to_string_pretty(&theme)?;
+    Ok(format!("{}\n", json))
+}
+
+fn write_export(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    fs::write(path, content).with_context(|| format!("Failed to write: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::models::Base16;
+
+    fn create_test_palette() -> Palette {
+        Palette {
+            name: "Test Scheme".to_string(),
+            base: None,
+            base_30: None,
+            base_16: Some(Base16 {
+                base00: "000000".to_string(),
+                base01: "111111".to_string(),
+                base02: "222222".to_string(),
+                base03: "333333".to_string(),
+                base04: "444444".to_string(),
+                base05: "555555".to_string(),
+                base06: "666666".to_string(),
+                base07: "777777".to_string(),
+                base08: "888888".to_string(),
+                base09: "999999".to_string(),
+                base0a: "aaaaaa".to_string(),
+                base0b: "bbbbbb".to_string(),
+                base0c: "cccccc".to_string(),
+                base0d: "dddddd".to_string(),
+                base0e: "eeeeee".to_string(),
+                base0f: "ffffff".to_string(),
+            }),
+            colors: None,
+        }
+    }
+
+    #[test]
+    fn test_export_base16_yaml_has_every_key() {
+        let yaml = export(&create_test_palette(), "base16-yaml").unwrap();
+        let scheme: serde_yaml::Mapping = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(scheme.get("scheme").unwrap().as_str(), Some("Test Scheme"));
+        for (key, color) in create_test_palette().base16().unwrap().named_colors() {
+            assert_eq!(scheme.get(key).unwrap().as_str(), Some(color), "{}", key);
+        }
+        assert_eq!(scheme.len(), 17);
+    }
+
+    #[test]
+    fn test_export_base16_yaml_round_trips() {
+        let yaml = export(&create_test_palette(), "base16-yaml").unwrap();
+        let palette = Palette::from_base16_scheme(&yaml).unwrap();
+
+        assert_eq!(palette.name, "Test Scheme");
+        assert_eq!(palette.base16().unwrap().base0a, "aaaaaa");
+    }
+
+    #[test]
+    fn test_export_pywal_json() {
+        let json = export(&create_test_palette(), "pywal-json").unwrap();
+        let theme: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(theme["special"]["background"], "#000000");
+        assert_eq!(theme["special"]["foreground"], "#555555");
+        assert_eq!(theme["colors"]["color0"], "#000000");
+        assert_eq!(theme["colors"]["color1"], "#888888");
+        assert_eq!(theme["colors"]["color15"], "#777777");
+        assert_eq!(theme["colors"].as_object().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_export_themer_json_round_trips() {
+        let json = export(&create_test_palette(), "themer-json").unwrap();
+        let palette: Palette = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(palette.name, "Test Scheme");
+        assert_eq!(palette.base16().unwrap().base0f, "ffffff");
+    }
+
+    #[test]
+    fn test_export_unknown_format() {
+        let result = export(&create_test_palette(), "kitty");
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown format 'kitty'")
+        );
+    }
+}
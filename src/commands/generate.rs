@@ -0,0 +1,78 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::extract;
+use crate::palette::loader::PaletteLoader;
+
+pub fn execute(wallpaper: &str, name: &str) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, wallpaper, name)
+}
+
+fn run(config_loader: &ConfigLoader, wallpaper: &str, name: &str) -> Result<()> {
+    let palette_loader = PaletteLoader::new(config_loader.config_dir());
+
+    if palette_loader.exists(name) {
+        anyhow::bail!("Palette '{}' already exists", name);
+    }
+
+    let wallpaper_path = shellexpand::tilde(wallpaper).into_owned();
+    output::header(&format!("Generating palette from {}", wallpaper_path));
+
+    let palette = extract::palette_from_image(Path::new(&wallpaper_path), name)?;
+    palette_loader.save(&palette)?;
+
+    if let Ok(base16) = palette.base16() {
+        for (key, color) in base16.named_colors() {
+            output::item(None, key, Some(color));
+        }
+    }
+
+    output::success(&format!("Created palette '{}'", name));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::setup_config_dir;
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader, String) {
+        let (temp_dir, loader) = setup_config_dir();
+
+        let wallpaper = temp_dir.path().join("wallpaper.png");
+        RgbImage::from_pixel(4, 4, Rgb([0x1d, 0x20, 0x21]))
+            .save(&wallpaper)
+            .unwrap();
+
+        (temp_dir, loader, wallpaper.display().to_string())
+    }
+
+    #[test]
+    fn test_generate_writes_loadable_palette() {
+        let (_temp, loader, wallpaper) = setup_test_env();
+
+        run(&loader, &wallpaper, "from-wall").unwrap();
+
+        let palette = PaletteLoader::new(loader.config_dir())
+            .load("from-wall")
+            .unwrap();
+        assert_eq!(palette.name, "from-wall");
+        assert_eq!(palette.base16().unwrap().base00, "1d2021");
+    }
+
+    #[test]
+    fn test_generate_refuses_to_overwrite() {
+        let (_temp, loader, wallpaper) = setup_test_env();
+        run(&loader, &wallpaper, "from-wall").unwrap();
+
+        let result = run(&loader, &wallpaper, "from-wall");
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::Config;
+use crate::output::output;
+
+pub fn execute(force: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, force)
+}
+
+fn run(config_loader: &ConfigLoader, force: bool) -> Result<()> {
+    let config_dir = config_loader.config_dir();
+    output::header(&format!("Initializing {}", config_dir.display()));
+
+    for dir in ["palettes", "templates"] {
+        let path = config_dir.join(dir);
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+        output::item(Some("dir"), &format!("{}/", dir), None);
+    }
+
+    let config_path = config_dir.join("config.toml");
+
+    if config_path.exists() && !force {
+        output::warning(&format!(
+            "{} already exists, use --force to overwrite",
+            config_path.display()
+        ));
+        return Ok(());
+    }
+
+    config_loader.save(&Config::default())?;
+    output::item(Some("file"), "config.toml", None);
+
+    output::success("Themer initialized");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_config_loader;
+
+    #[test]
+    fn test_init_creates_dirs_and_config() {
+        let (_temp, loader) = temp_config_loader();
+
+        run(&loader, false).unwrap();
+
+        assert!(loader.config_dir().join("palettes").is_dir());
+        assert!(loader.config_dir().join("templates").is_dir());
+
+        let config = loader.load().unwrap();
+        assert_eq!(config.active_palette, "");
+        assert!(config.targets.is_empty());
+    }
+
+    #[test]
+    fn test_init_does_not_overwrite_without_force() {
+        let (_temp, loader) = temp_config_loader();
+        run(&loader, false).unwrap();
+
+        let config_path = loader.config_dir().join("config.toml");
+        fs::write(&config_path, "active_palette = \"nord\"\ntargets = []\n").unwrap();
+
+        run(&loader, false).unwrap();
+
+        assert_eq!(loader.load().unwrap().active_palette, "nord");
+    }
+
+    #[test]
+    fn test_init_force_overwrites_config() {
+        let (_temp, loader) = temp_config_loader();
+        run(&loader, false).unwrap();
+
+        let config_path = loader.config_dir().join("config.toml");
+        fs::write(&config_path, "active_palette = \"nord\"\ntargets = []\n").unwrap();
+
+        run(&loader, true).unwrap();
+
+        assert_eq!(loader.load().unwrap().active_palette, "");
+    }
+}
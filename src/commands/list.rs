@@ -1,44 +1,108 @@
 use anyhow::Result;
+use serde::Serialize;
 
 use crate::config::loader::ConfigLoader;
 use crate::output::output;
-use crate::palette::loader::PaletteLoader;
+use crate::palette::loader::{PaletteInfo, PaletteLoader};
 
-pub fn execute(format: Option<&str>) -> Result<()> {
+/// TOML has no top-level arrays, so the list is wrapped in a `palettes` table.
+#[derive(Serialize)]
+struct PaletteList<'a> {
+    palettes: &'a [PaletteInfo],
+}
+
+/// Which color sets a palette provides, or `None` when it fails to load.
+#[derive(Debug, PartialEq, Eq)]
+struct PaletteDetails {
+    filename: String,
+    colors: Option<(bool, bool)>,
+}
+
+pub fn execute(
+    format: Option<&str>,
+    long: bool,
+    sort: Option<&str>,
+    filter: Option<&str>,
+) -> Result<()> {
     let config_loader = ConfigLoader::new()?;
-    let palette_loader = PaletteLoader::new(config_loader.config_dir());
-    let palettes = palette_loader.list_all()?;
+    let config = config_loader.load_or_default()?;
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+    let mut palettes = palette_loader.list_all()?;
+
+    if let Some(filter) = filter {
+        filter_palettes(&mut palettes, filter);
+    }
+    if let Some(sort) = sort {
+        sort_palettes(&mut palettes, sort);
+    }
 
     match format {
         Some("plain") => output_plain(&palettes),
         Some("json") => output_json(&palettes)?,
+        Some("toml") => print!("{}", to_toml(&palettes)?),
         Some("preview") => output_preview(&palette_loader, &palettes)?,
         Some(unknown) => {
             output::warning(&format!("Unknown format '{}', using default", unknown));
             output_default(&palettes)
         }
+        None if long => output_long(&palette_details(&palette_loader, &palettes)),
         None => output_default(&palettes),
     }
 
     Ok(())
 }
 
-fn output_plain(palettes: &[crate::palette::loader::PaletteInfo]) {
+/// Keeps palettes whose filename or name contains `filter`, ignoring case.
+fn filter_palettes(palettes: &mut Vec<PaletteInfo>, filter: &str) {
+    let filter = filter.to_lowercase();
+    palettes.retain(|info| {
+        info.filename.to_lowercase().contains(&filter)
+            || info
+                .name
+                .as_ref()
+                .is_some_and(|name| name.to_lowercase().contains(&filter))
+    });
+}
+
+/// Sorts by `name` (palettes that failed to load last) or `filename`. Ties
+/// fall back to the filename so the order is stable.
+fn sort_palettes(palettes: &mut [PaletteInfo], sort: &str) {
+    match sort {
+        "name" => palettes.sort_by(|a, b| {
+            let key = |info: &PaletteInfo| info.name.as_ref().map(|name| name.to_lowercase());
+            match (key(a), key(b)) {
+                (Some(a_name), Some(b_name)) => a_name.cmp(&b_name),
+                (a_name, b_name) => b_name.is_some().cmp(&a_name.is_some()),
+            }
+            .then_with(|| a.filename.cmp(&b.filename))
+        }),
+        "filename" => palettes.sort_by(|a, b| a.filename.cmp(&b.filename)),
+        unknown => output::warning(&format!(
+            "Unknown sort '{}', expected 'name' or 'filename'",
+            unknown
+        )),
+    }
+}
+
+fn output_plain(palettes: &[PaletteInfo]) {
     for info in palettes {
         println!("{}", info.filename);
     }
 }
 
-fn output_json(palettes: &[crate::palette::loader::PaletteInfo]) -> Result<()> {
+fn output_json(palettes: &[PaletteInfo]) -> Result<()> {
     let names: Vec<&str> = palettes.iter().map(|p| p.filename.as_str()).collect();
     println!("{}", serde_json::to_string_pretty(&names)?);
     Ok(())
 }
 
-fn output_preview(
-    palette_loader: &PaletteLoader,
-    palettes: &[crate::palette::loader::PaletteInfo],
-) -> Result<()> {
+fn to_toml(palettes: &[PaletteInfo]) -> Result<String> {
+    Ok(toml::to_string_pretty(&PaletteList { palettes })?)
+}
+
+fn output_preview(palette_loader: &PaletteLoader, palettes: &[PaletteInfo]) -> Result<()> {
     let mut json_output = serde_json::Map::new();
 
     for palette_info in palettes {
@@ -65,7 +129,7 @@ fn output_preview(
     Ok(())
 }
 
-fn output_default(palettes: &[crate::palette::loader::PaletteInfo]) {
+fn output_default(palettes: &[PaletteInfo]) {
     output::header("Available palettes:");
 
     if palettes.is_empty() {
@@ -78,9 +142,44 @@ fn output_default(palettes: &[crate::palette::loader::PaletteInfo]) {
     }
 }
 
+fn output_long(details: &[PaletteDetails]) {
+    output::header("Available palettes:");
+
+    if details.is_empty() {
+        output::warning("No palettes found");
+        return;
+    }
+
+    for detail in details {
+        let description = match detail.colors {
+            Some((true, true)) => "base16, base30",
+            Some((true, false)) => "base16",
+            Some((false, true)) => "base30",
+            Some((false, false)) => "no colors",
+            None => "invalid",
+        };
+        output::item(Some("Palette"), &detail.filename, Some(description));
+    }
+}
+
+fn palette_details(
+    palette_loader: &PaletteLoader,
+    palettes: &[PaletteInfo],
+) -> Vec<PaletteDetails> {
+    palettes
+        .iter()
+        .map(|info| PaletteDetails {
+            filename: info.filename.clone(),
+            colors: palette_loader
+                .load(&info.filename)
+                .ok()
+                .map(|palette| (palette.base_16.is_some(), palette.base_30.is_some())),
+        })
+        .collect()
+}
+
 fn format_display_name(name: &str) -> String {
-    name.replace('-', " ")
-        .replace('_', " ")
+    name.replace(['-', '_'], " ")
         .split_whitespace()
         .map(|word| {
             let mut chars = word.chars();
@@ -92,3 +191,139 @@ fn format_display_name(name: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{
+        SAMPLE_BASE16, setup_config_dir, write_palette, write_sample_palette,
+    };
+
+    const BASE30: &str = r#"{
+        "white": "ffffff", "darker_black": "000000", "black": "111111", "black2": "1a1a1a",
+        "one_bg": "222222", "one_bg2": "2a2a2a", "one_bg3": "333333", "grey": "888888",
+        "grey_fg": "999999", "grey_fg2": "aaaaaa", "light_grey": "bbbbbb", "red": "ff0000",
+        "baby_pink": "ffaaaa", "pink": "ff00ff", "line": "444444", "green": "00ff00",
+        "vibrant_green": "00ff88", "nord_blue": "5e81ac", "blue": "0000ff", "yellow": "ffff00",
+        "sun": "ffaa00", "purple": "aa00ff", "dark_purple": "550088", "teal": "00ffff",
+        "orange": "ff8800", "cyan": "00aaff", "lightbg": "eeeeee"
+    }"#;
+
+    #[test]
+    fn test_palette_details_reports_color_sets() {
+        let (_temp, config_loader) = setup_config_dir();
+        write_sample_palette(&config_loader, "lean");
+        write_palette(
+            &config_loader,
+            "full",
+            &format!(
+                r#"{{"name": "Full", "base_16": {}, "base_30": {}}}"#,
+                SAMPLE_BASE16, BASE30
+            ),
+        );
+        write_palette(&config_loader, "broken", "{ not json");
+
+        let loader = PaletteLoader::new(config_loader.config_dir());
+        let mut palettes = loader.list_all().unwrap();
+        palettes.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+        let details = palette_details(&loader, &palettes);
+
+        assert_eq!(
+            details,
+            vec![
+                PaletteDetails {
+                    filename: "broken".to_string(),
+                    colors: None,
+                },
+                PaletteDetails {
+                    filename: "full".to_string(),
+                    colors: Some((true, true)),
+                },
+                PaletteDetails {
+                    filename: "lean".to_string(),
+                    colors: Some((true, false)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_toml_round_trips() {
+        let palettes = vec![
+            PaletteInfo {
+                filename: "gruvbox".to_string(),
+                name: Some("Gruvbox".to_string()),
+            },
+            PaletteInfo {
+                filename: "broken".to_string(),
+                name: None,
+            },
+        ];
+
+        let output = to_toml(&palettes).unwrap();
+        let table: toml::Table = toml::from_str(&output).unwrap();
+        let parsed: Vec<PaletteInfo> = table["palettes"].clone().try_into().unwrap();
+
+        assert_eq!(parsed, palettes);
+    }
+
+    fn info(filename: &str, name: Option<&str>) -> PaletteInfo {
+        PaletteInfo {
+            filename: filename.to_string(),
+            name: name.map(String::from),
+        }
+    }
+
+    fn filenames(palettes: &[PaletteInfo]) -> Vec<&str> {
+        palettes.iter().map(|p| p.filename.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_palettes_by_name_puts_invalid_last() {
+        let mut palettes = vec![
+            info("broken", None),
+            info("tokyo", Some("Tokyo Night")),
+            info("another-broken", None),
+            info("gruvbox", Some("gruvbox dark")),
+            info("nord", Some("Nord")),
+        ];
+
+        sort_palettes(&mut palettes, "name");
+
+        assert_eq!(
+            filenames(&palettes),
+            vec!["gruvbox", "nord", "tokyo", "another-broken", "broken"]
+        );
+    }
+
+    #[test]
+    fn test_sort_palettes_by_filename() {
+        let mut palettes = vec![
+            info("tokyo", Some("A")),
+            info("dark/nord", None),
+            info("gruvbox", Some("B")),
+        ];
+
+        sort_palettes(&mut palettes, "filename");
+
+        assert_eq!(filenames(&palettes), vec!["dark/nord", "gruvbox", "tokyo"]);
+    }
+
+    #[test]
+    fn test_filter_palettes_matches_filename_or_name() {
+        let mut palettes = vec![
+            info("gruvbox", Some("Gruvbox Dark")),
+            info("gruvbox-light", Some("Gruvbox Light")),
+            info("tokyo", Some("Tokyo Night")),
+            info("broken", None),
+        ];
+
+        filter_palettes(&mut palettes, "DARK");
+        assert_eq!(filenames(&palettes), vec!["gruvbox"]);
+
+        let mut palettes = vec![info("gruvbox", None), info("nord", None)];
+        filter_palettes(&mut palettes, "gruv");
+        assert_eq!(filenames(&palettes), vec!["gruvbox"]);
+    }
+}
@@ -1,8 +1,17 @@
 use anyhow::Result;
+use serde::Serialize;
 
 use crate::config::loader::ConfigLoader;
+use crate::config::models::Target;
 use crate::output::output;
 
+/// TOML has no top-level arrays, so the targets are wrapped the same way
+/// config.toml stores them, as `[[targets]]` entries.
+#[derive(Serialize)]
+struct TargetList<'a> {
+    targets: &'a [Target],
+}
+
 pub fn execute(format: Option<&str>) -> Result<()> {
     let config_loader = ConfigLoader::new()?;
     let config = config_loader.load()?;
@@ -10,6 +19,7 @@ pub fn execute(format: Option<&str>) -> Result<()> {
     match format {
         Some("plain") => output_plain(&config.targets),
         Some("json") => output_json(&config.targets)?,
+        Some("toml") => print!("{}", to_toml(&config.targets)?),
         Some(unknown) => {
             output::warning(&format!("Unknown format '{}', using default", unknown));
             output_default(&config.targets)
@@ -20,18 +30,22 @@ pub fn execute(format: Option<&str>) -> Result<()> {
     Ok(())
 }
 
-fn output_plain(targets: &[crate::config::models::Target]) {
+fn output_plain(targets: &[Target]) {
     for target in targets {
         println!("{}", target.name);
     }
 }
 
-fn output_json(targets: &[crate::config::models::Target]) -> Result<()> {
+fn output_json(targets: &[Target]) -> Result<()> {
     println!("{}", serde_json::to_string_pretty(targets)?);
     Ok(())
 }
 
-fn output_default(targets: &[crate::config::models::Target]) {
+fn to_toml(targets: &[Target]) -> Result<String> {
+    Ok(toml::to_string_pretty(&TargetList { targets })?)
+}
+
+fn output_default(targets: &[Target]) {
     output::header("Configured targets:");
 
     if targets.is_empty() {
@@ -40,7 +54,9 @@ fn output_default(targets: &[crate::config::models::Target]) {
     }
 
     for target in targets {
-        output::item(Some("Target"), &target.name, Some(&target.template));
+        let badge = if target.enabled { "Target" } else { "Disabled" };
+        let templates: Vec<&str> = target.template_files().collect();
+        output::item(Some(badge), &target.name, Some(&templates.join(", ")));
 
         let mode_str = format!("Mode: {:?}", target.mode);
         output::info(&mode_str);
@@ -58,3 +74,40 @@ fn output_default(targets: &[crate::config::models::Target]) {
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::models::Mode;
+
+    #[test]
+    fn test_to_toml_round_trips() {
+        let targets = vec![
+            Target {
+                name: "kitty".to_string(),
+                template: "kitty.conf".to_string(),
+                reload_cmd: "kill -SIGUSR1 $(pgrep kitty)".to_string(),
+                ..Default::default()
+            },
+            Target {
+                name: "alacritty".to_string(),
+                template: "alacritty.toml".to_string(),
+                output: "~/.config/alacritty/colors.toml".to_string(),
+                mode: Mode::Replace,
+                enabled: false,
+                ..Default::default()
+            },
+        ];
+
+        let output = to_toml(&targets).unwrap();
+        let table: toml::Table = toml::from_str(&output).unwrap();
+        let parsed: Vec<Target> = table["targets"].clone().try_into().unwrap();
+
+        assert!(output.contains("[[targets]]"));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "kitty");
+        assert_eq!(parsed[0].reload_cmd, targets[0].reload_cmd);
+        assert_eq!(parsed[1].mode, Mode::Replace);
+        assert!(!parsed[1].enabled);
+    }
+}
@@ -1,4 +1,25 @@
+pub mod add_target;
 pub mod apply;
+pub mod cache;
+pub mod check;
+pub mod completion;
+pub mod diff;
+pub mod doctor;
+pub mod edit;
+pub mod export;
+pub mod generate;
+pub mod init;
 pub mod list;
 pub mod list_targets;
+pub mod new_palette;
+pub mod preview;
+pub mod random;
+pub mod remove_target;
+pub mod render;
+pub mod revert;
+pub mod scan_templates;
 pub mod validate;
+pub mod watch;
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,118 @@
+use anyhow::Result;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+use crate::palette::models::{Base16, Base30, Palette};
+
+/// Value every slot of a scaffolded palette starts with.
+const PLACEHOLDER: &str = "000000";
+
+pub fn execute(name: &str, base: &str, force: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, name, base, force)
+}
+
+fn run(config_loader: &ConfigLoader, name: &str, base: &str, force: bool) -> Result<()> {
+    let palette = scaffold(name, base)?;
+    let palette_loader = PaletteLoader::new(config_loader.config_dir());
+
+    if palette_loader.palette_path(name).exists() && !force {
+        anyhow::bail!(
+            "Palette '{}' already exists. Use --force to overwrite",
+            name
+        );
+    }
+
+    let palette_path = palette_loader.save(&palette)?;
+
+    output::success(&format!(
+        "Created palette '{}' at {}",
+        name,
+        palette_path.display()
+    ));
+    output::info("Every color is a placeholder; fill them in before applying");
+    Ok(())
+}
+
+fn scaffold(name: &str, base: &str) -> Result<Palette> {
+    let (base_16, base_30) = match base {
+        "base16" => (true, false),
+        "base30" => (false, true),
+        "both" => (true, true),
+        unknown => anyhow::bail!(
+            "Unknown base '{}', expected 'base16', 'base30' or 'both'",
+            unknown
+        ),
+    };
+
+    Ok(Palette {
+        name: name.to_string(),
+        base: None,
+        base_16: base_16.then(|| Base16::filled(PLACEHOLDER)),
+        base_30: base_30.then(|| Base30::filled(PLACEHOLDER)),
+        colors: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_config_loader;
+    use std::fs;
+
+    #[test]
+    fn test_new_palette_loads_and_validates() {
+        let (_temp, loader) = temp_config_loader();
+
+        run(&loader, "scratch", "both", false).unwrap();
+
+        let palette = PaletteLoader::new(loader.config_dir())
+            .load("scratch")
+            .unwrap();
+        assert!(palette.validate().is_ok());
+        assert_eq!(palette.name, "scratch");
+        assert_eq!(palette.base16().unwrap().base0f, PLACEHOLDER);
+        assert_eq!(palette.base30().unwrap().lightbg, PLACEHOLDER);
+    }
+
+    #[test]
+    fn test_new_palette_uses_palette_file_keys() {
+        let (_temp, loader) = temp_config_loader();
+
+        run(&loader, "scratch", "base16", false).unwrap();
+
+        let content =
+            fs::read_to_string(loader.config_dir().join("palettes").join("scratch.json")).unwrap();
+        assert!(content.contains("\"base0A\""));
+        assert!(content.contains("\"base0F\""));
+        assert!(!content.contains("base_30"));
+    }
+
+    #[test]
+    fn test_new_palette_refuses_to_overwrite() {
+        let (_temp, loader) = temp_config_loader();
+        run(&loader, "scratch", "base30", false).unwrap();
+
+        let result = run(&loader, "scratch", "base30", false);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("already exists"));
+        assert!(run(&loader, "scratch", "base16", true).is_ok());
+    }
+
+    #[test]
+    fn test_new_palette_unknown_base() {
+        let (_temp, loader) = temp_config_loader();
+
+        let result = run(&loader, "scratch", "base24", false);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown base 'base24'")
+        );
+    }
+}
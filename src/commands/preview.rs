@@ -0,0 +1,130 @@
+use anyhow::{Context, Result};
+
+use crate::config::loader::ConfigLoader;
+use crate::palette::loader::PaletteLoader;
+use crate::target::processor::{TargetProcessor, template_parts};
+use crate::template::engine::TemplateEngine;
+
+pub fn execute(target_name: &str, palette_name: &str) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    let rendered = render(&config_loader, target_name, palette_name)?;
+
+    print!("{}", rendered);
+    Ok(())
+}
+
+fn render(config_loader: &ConfigLoader, target_name: &str, palette_name: &str) -> Result<String> {
+    let config = config_loader.load()?;
+
+    let target = config
+        .targets
+        .iter()
+        .find(|t| t.name == target_name)
+        .with_context(|| format!("Target '{}' not found in configuration", target_name))?;
+
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+    let palette = palette_loader
+        .load(palette_name)
+        .with_context(|| format!("Failed to load palette '{}'", palette_name))?;
+
+    let engine = TemplateEngine::new();
+    let context = engine.create_target_context(&palette, palette_name, &config, &palette_loader)?;
+    let mut processor = TargetProcessor::new(config_loader.config_dir());
+
+    let parts = template_parts(target);
+    if let [part] = parts.as_slice() {
+        return processor.render(part, &context);
+    }
+
+    // Several templates are shown one after another, each under a
+    // `==> template <==` line.
+    let mut rendered = String::new();
+    for part in &parts {
+        rendered.push_str(&format!("==> {} <==\n", part.template));
+        rendered.push_str(&processor.render(part, &context)?);
+        if !rendered.ends_with('\n') {
+            rendered.push('\n');
+        }
+    }
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_sample_palette, write_template};
+    use crate::config::models::{Config, Target};
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_sample_palette(&loader, "test");
+        write_template(&loader, "bar.css", "bg: {{ base00 }}");
+
+        let config = Config {
+            active_palette: String::new(),
+            targets: vec![Target {
+                name: "bar".to_string(),
+                template: "bar.css".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_preview_renders_template() {
+        let (_temp, loader) = setup_test_env();
+
+        let rendered = render(&loader, "bar", "test").unwrap();
+
+        assert_eq!(rendered, "bg: 000000");
+    }
+
+    #[test]
+    fn test_preview_exposes_previous_palette() {
+        let (_temp, loader) = setup_test_env();
+        write_sample_palette(&loader, "old");
+        write_template(&loader, "bar.css", "from: {{ prev_name }}");
+        let mut config = loader.load().unwrap();
+        config.active_palette = "old".to_string();
+        loader.save(&config).unwrap();
+
+        let rendered = render(&loader, "bar", "test").unwrap();
+
+        assert_eq!(rendered, "from: old");
+    }
+
+    #[test]
+    fn test_preview_unknown_target() {
+        let (_temp, loader) = setup_test_env();
+
+        let result = render(&loader, "kitty", "test");
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Target 'kitty' not found")
+        );
+    }
+
+    #[test]
+    fn test_preview_unknown_palette() {
+        let (_temp, loader) = setup_test_env();
+
+        let result = render(&loader, "bar", "missing");
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to load palette 'missing'")
+        );
+    }
+}
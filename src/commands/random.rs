@@ -0,0 +1,137 @@
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::commands::apply::{self, ApplyOptions};
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+
+pub fn execute(seed: Option<u64>, exclude_current: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, seed, exclude_current)
+}
+
+fn run(config_loader: &ConfigLoader, seed: Option<u64>, exclude_current: bool) -> Result<()> {
+    let config = config_loader.load()?;
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+    let current = palette_loader.resolve(&config.active_palette).to_string();
+
+    let mut palettes: Vec<String> = palette_loader
+        .list_all()?
+        .into_iter()
+        .filter(|p| p.name.is_some())
+        .map(|p| p.filename)
+        .collect();
+    // Directory order is unspecified, so sort to keep seeded picks stable.
+    palettes.sort();
+
+    if exclude_current {
+        palettes.retain(|name| *name != current);
+    }
+
+    let chosen = match seed {
+        Some(seed) => choose(&palettes, &mut StdRng::seed_from_u64(seed)),
+        None => choose(&palettes, &mut rand::thread_rng()),
+    };
+
+    let Some(chosen) = chosen else {
+        if exclude_current {
+            anyhow::bail!("No palettes available other than '{}'", current);
+        }
+        anyhow::bail!("No palettes available");
+    };
+
+    output::info(&format!("Picked palette '{}'", chosen));
+    apply::run(config_loader, &chosen, &ApplyOptions::default())
+}
+
+fn choose(palettes: &[String], rng: &mut impl Rng) -> Option<String> {
+    palettes.choose(rng).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{
+        app_target, setup_config_dir, write_sample_palette, write_template,
+    };
+    use crate::config::models::Config;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_test_env(active_palette: &str) -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_template(&loader, "colors.conf", "{{ name }}");
+        for name in ["gruvbox", "nord", "tokyo"] {
+            write_sample_palette(&loader, name);
+        }
+
+        let config = Config {
+            active_palette: active_palette.to_string(),
+            targets: vec![app_target(&temp_dir.path().join("colors.conf"))],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_seeded_choice_is_stable() {
+        let palettes: Vec<String> = ["gruvbox", "nord", "tokyo"].map(String::from).to_vec();
+
+        let first = choose(&palettes, &mut StdRng::seed_from_u64(42));
+        let second = choose(&palettes, &mut StdRng::seed_from_u64(42));
+
+        assert!(first.is_some());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_random_excludes_current_palette() {
+        for seed in 0..16 {
+            let (_temp, loader) = setup_test_env("nord");
+
+            run(&loader, Some(seed), true).unwrap();
+
+            let active = loader.load().unwrap().active_palette;
+            assert_ne!(active, "nord");
+            assert!(active == "gruvbox" || active == "tokyo");
+        }
+    }
+
+    #[test]
+    fn test_random_same_seed_picks_same_palette() {
+        let (_temp_a, loader_a) = setup_test_env("nord");
+        let (_temp_b, loader_b) = setup_test_env("nord");
+
+        run(&loader_a, Some(7), true).unwrap();
+        run(&loader_b, Some(7), true).unwrap();
+
+        assert_eq!(
+            loader_a.load().unwrap().active_palette,
+            loader_b.load().unwrap().active_palette
+        );
+    }
+
+    #[test]
+    fn test_random_fails_when_only_current_palette_exists() {
+        let (_temp, loader) = setup_test_env("nord");
+        let palettes_dir = loader.config_dir().join("palettes");
+        fs::remove_file(palettes_dir.join("gruvbox.json")).unwrap();
+        fs::remove_file(palettes_dir.join("tokyo.json")).unwrap();
+
+        let result = run(&loader, Some(1), true);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No palettes available other than 'nord'")
+        );
+    }
+}
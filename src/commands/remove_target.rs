@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+
+pub fn execute(name: &str) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader, name)
+}
+
+fn run(config_loader: &ConfigLoader, name: &str) -> Result<()> {
+    let mut config = config_loader.load()?;
+
+    let Some(index) = config.targets.iter().position(|t| t.name == name) else {
+        anyhow::bail!("Target '{}' not found", name);
+    };
+
+    let removed = config.targets.remove(index);
+    config_loader.save(&config)?;
+
+    output::success(&format!("Removed target '{}'", removed.name));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::setup_config_dir;
+    use crate::config::models::{Config, Mode, Target};
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+
+        let config = Config {
+            active_palette: String::new(),
+            targets: ["kitty", "waybar"]
+                .iter()
+                .map(|name| Target {
+                    name: name.to_string(),
+                    template: format!("{}.tmpl", name),
+                    output: String::new(),
+                    mode: Mode::Include,
+                    reload_cmd: String::new(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_remove_target() {
+        let (_temp, loader) = setup_test_env();
+
+        run(&loader, "kitty").unwrap();
+
+        let config = loader.load().unwrap();
+        assert_eq!(config.targets.len(), 1);
+        assert_eq!(config.targets[0].name, "waybar");
+    }
+
+    #[test]
+    fn test_remove_target_not_found() {
+        let (_temp, loader) = setup_test_env();
+
+        let result = run(&loader, "alacritty");
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Target 'alacritty' not found"
+        );
+        assert_eq!(loader.load().unwrap().targets.len(), 2);
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::config::loader::ConfigLoader;
+use crate::palette::loader::PaletteLoader;
+use crate::template::engine::TemplateEngine;
+
+/// Template name used in error messages for a template read from stdin.
+const STDIN_TEMPLATE: &str = "<stdin>";
+
+pub fn execute(palette_name: &str, template_file: Option<&Path>) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+
+    let (template_name, template) = match template_file {
+        Some(path) if path != Path::new("-") => {
+            let template = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read template: {}", path.display()))?;
+            (path.display().to_string(), template)
+        }
+        _ => {
+            let mut template = String::new();
+            io::stdin()
+                .read_to_string(&mut template)
+                .context("Failed to read template from stdin")?;
+            (STDIN_TEMPLATE.to_string(), template)
+        }
+    };
+
+    print!(
+        "{}",
+        run(&config_loader, palette_name, &template_name, &template)?
+    );
+    Ok(())
+}
+
+/// Renders a one-off template against a palette, without a target. Partials
+/// from the templates directory are available to `{% include %}`.
+fn run(
+    config_loader: &ConfigLoader,
+    palette_name: &str,
+    template_name: &str,
+    template: &str,
+) -> Result<String> {
+    let config = config_loader.load()?;
+    let palette_loader = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?;
+    let palette = palette_loader
+        .load(palette_name)
+        .with_context(|| format!("Failed to load palette '{}'", palette_name))?;
+
+    let mut engine = TemplateEngine::new();
+    engine.load_partials(&config_loader.config_dir().join("templates"))?;
+    let context = engine.create_target_context(&palette, palette_name, &config, &palette_loader)?;
+
+    engine.render(template_name, template, &context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_sample_palette};
+    use crate::config::models::Config;
+    use crate::template::engine::PARTIALS_DIR;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_sample_palette(&loader, "test");
+        fs::create_dir_all(loader.config_dir().join("templates").join(PARTIALS_DIR)).unwrap();
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_render_snippet() {
+        let (_temp, loader) = setup_test_env();
+
+        let rendered = run(&loader, "test", STDIN_TEMPLATE, "{{ base00 | hex_hash }}").unwrap();
+
+        assert_eq!(rendered, "#000000");
+    }
+
+    #[test]
+    fn test_render_snippet_with_partial() {
+        let (_temp, loader) = setup_test_env();
+        fs::write(
+            loader
+                .config_dir()
+                .join("templates")
+                .join(PARTIALS_DIR)
+                .join("bg.tmpl"),
+            "bg={{ base00 }}",
+        )
+        .unwrap();
+
+        let rendered = run(
+            &loader,
+            "test",
+            STDIN_TEMPLATE,
+            r#"{% include "partials/bg.tmpl" %}"#,
+        )
+        .unwrap();
+
+        assert_eq!(rendered, "bg=000000");
+    }
+
+    #[test]
+    fn test_render_snippet_with_settings() {
+        let (_temp, loader) = setup_test_env();
+        let config = Config {
+            opacity: 0.9,
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        let rendered = run(&loader, "test", STDIN_TEMPLATE, "opacity={{ opacity }}").unwrap();
+
+        assert_eq!(rendered, "opacity=0.9");
+    }
+
+    #[test]
+    fn test_render_snippet_with_previous_palette() {
+        let (_temp, loader) = setup_test_env();
+        write_sample_palette(&loader, "old");
+        let config = Config {
+            active_palette: "test".to_string(),
+            previous_palette: "old".to_string(),
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+
+        let rendered = run(&loader, "test", STDIN_TEMPLATE, "{{ prev_name }}").unwrap();
+
+        assert_eq!(rendered, "old");
+    }
+
+    #[test]
+    fn test_render_missing_palette() {
+        let (_temp, loader) = setup_test_env();
+
+        let result = run(&loader, "missing", STDIN_TEMPLATE, "{{ base00 }}");
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to load palette 'missing'")
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+
+use crate::commands::apply::{self, ApplyOptions};
+use crate::config::loader::ConfigLoader;
+
+pub fn execute() -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    run(&config_loader)
+}
+
+/// Reapplies the previous palette. Applying records the current palette as
+/// the new previous one, so reverting twice swaps back.
+fn run(config_loader: &ConfigLoader) -> Result<()> {
+    let config = config_loader.load()?;
+
+    if config.previous_palette.is_empty() {
+        anyhow::bail!("No previous palette to revert to");
+    }
+
+    apply::run(
+        config_loader,
+        &config.previous_palette,
+        &ApplyOptions::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{
+        app_target, setup_config_dir, write_sample_palette, write_template,
+    };
+    use crate::config::models::{Config, Target};
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader, PathBuf) {
+        let (temp_dir, loader) = setup_config_dir();
+        write_template(&loader, "colors.conf", "{{ name }}");
+        for name in ["alpha", "beta"] {
+            write_sample_palette(&loader, name);
+        }
+
+        let output_path = temp_dir.path().join("colors.conf");
+        let config = Config {
+            active_palette: String::new(),
+            targets: vec![Target {
+                header: false,
+                ..app_target(&output_path)
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+        (temp_dir, loader, output_path)
+    }
+
+    #[test]
+    fn test_revert_round_trip() {
+        let (_temp, loader, output_path) = setup_test_env();
+
+        apply::run(&loader, "alpha", &ApplyOptions::default()).unwrap();
+        apply::run(&loader, "beta", &ApplyOptions::default()).unwrap();
+        run(&loader).unwrap();
+
+        let config = loader.load().unwrap();
+        assert_eq!(config.active_palette, "alpha");
+        assert_eq!(config.previous_palette, "beta");
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "alpha");
+
+        run(&loader).unwrap();
+        assert_eq!(loader.load().unwrap().active_palette, "beta");
+    }
+
+    #[test]
+    fn test_revert_without_previous_palette_fails() {
+        let (_temp, loader, _) = setup_test_env();
+
+        let result = run(&loader);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No previous palette to revert to")
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::{Mode, Target};
+use crate::output::output;
+use crate::target::processor::strip_template_extension;
+use crate::template::engine::PARTIALS_DIR;
+
+pub fn execute(apply: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    output::header("Scanning templates...");
+
+    let added = run(&config_loader, apply)?;
+
+    if added.is_empty() {
+        output::success("Every template is used by a target");
+    } else if apply {
+        output::success(&format!("Added {} target(s)", added.len()));
+    } else {
+        output::info("Run with --apply to add them to config.toml");
+    }
+    Ok(())
+}
+
+/// Suggests an include-mode target for each template no target references
+/// yet, named after the file stem without any template marker
+/// (`kitty.conf.tmpl` → `kitty`), and saves them with `apply`.
+/// Partials are skipped, as are templates whose name is already taken by
+/// another target. Returns the suggested targets.
+fn run(config_loader: &ConfigLoader, apply: bool) -> Result<Vec<Target>> {
+    let mut config = config_loader.load()?;
+    let templates_dir = config_loader.config_dir().join("templates");
+
+    let mut templates = Vec::new();
+    collect_templates(&templates_dir, "", &mut templates)?;
+    templates.sort();
+
+    let mut suggested: Vec<Target> = Vec::new();
+    for template in templates {
+        if config
+            .targets
+            .iter()
+            .any(|t| t.template_files().any(|file| file == template))
+        {
+            continue;
+        }
+
+        let name = strip_template_extension(Path::new(&template))
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&template)
+            .to_string();
+        if config
+            .targets
+            .iter()
+            .chain(&suggested)
+            .any(|t| t.name == name)
+        {
+            output::warning(&format!(
+                "Skipping '{}': a target named '{}' already exists",
+                template, name
+            ));
+            continue;
+        }
+
+        output::item(Some("new"), &name, Some(&template));
+        suggested.push(Target {
+            name,
+            template,
+            mode: Mode::Include,
+            ..Default::default()
+        });
+    }
+
+    if apply && !suggested.is_empty() {
+        config.targets.extend(suggested.iter().cloned());
+        config_loader.save(&config)?;
+    }
+
+    Ok(suggested)
+}
+
+/// Collects template paths relative to the templates directory, the way
+/// targets refer to them, leaving out the partials directory.
+fn collect_templates(dir: &Path, prefix: &str, templates: &mut Vec<String>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(filename) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let name = format!("{}{}", prefix, filename);
+
+        if path.is_dir() {
+            if name != PARTIALS_DIR {
+                collect_templates(&path, &format!("{}/", name), templates)?;
+            }
+        } else {
+            templates.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{setup_config_dir, write_template};
+    use crate::config::models::Config;
+    use tempfile::TempDir;
+
+    fn setup_test_env() -> (TempDir, ConfigLoader) {
+        let (temp_dir, loader) = setup_config_dir();
+        fs::create_dir_all(loader.config_dir().join("templates").join(PARTIALS_DIR)).unwrap();
+        write_template(&loader, "kitty.conf", "");
+        write_template(&loader, "waybar.css.tmpl", "");
+        write_template(&loader, &format!("{}/base.tmpl", PARTIALS_DIR), "");
+
+        let config = Config {
+            targets: vec![Target {
+                name: "kitty".to_string(),
+                template: "kitty.conf".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_scan_suggests_only_unreferenced_templates() {
+        let (_temp, loader) = setup_test_env();
+
+        let suggested = run(&loader, false).unwrap();
+
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested[0].name, "waybar");
+        assert_eq!(suggested[0].template, "waybar.css.tmpl");
+        assert_eq!(suggested[0].mode, Mode::Include);
+        assert_eq!(loader.load().unwrap().targets.len(), 1);
+    }
+
+    #[test]
+    fn test_scan_apply_saves_targets() {
+        let (_temp, loader) = setup_test_env();
+
+        run(&loader, true).unwrap();
+
+        let targets = loader.load().unwrap().targets;
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[1].template, "waybar.css.tmpl");
+        assert!(run(&loader, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scan_skips_taken_names() {
+        let (_temp, loader) = setup_test_env();
+        fs::write(loader.config_dir().join("templates").join("kitty.tmpl"), "").unwrap();
+
+        let suggested = run(&loader, false).unwrap();
+
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested[0].template, "waybar.css.tmpl");
+    }
+}
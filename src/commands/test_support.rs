@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::config::loader::ConfigLoader;
+use crate::config::models::{Config, Mode, Target};
+
+/// Base16 colors of the sample palette: `base00` is `000000` and each slot
+/// steps by `111111` up to `base0F` = `ffffff`.
+pub const SAMPLE_BASE16: &str = r#"{
+    "base00": "000000", "base01": "111111", "base02": "222222", "base03": "333333",
+    "base04": "444444", "base05": "555555", "base06": "666666", "base07": "777777",
+    "base08": "888888", "base09": "999999", "base0A": "aaaaaa", "base0B": "bbbbbb",
+    "base0C": "cccccc", "base0D": "dddddd", "base0E": "eeeeee", "base0F": "ffffff"
+}"#;
+
+/// A palette file named `name` with the sample base16 colors.
+pub fn sample_palette(name: &str) -> String {
+    format!(r#"{{"name": "{}", "base_16": {}}}"#, name, SAMPLE_BASE16)
+}
+
+/// A loader for `<temp>/themer`. Nothing is created on disk, for tests that
+/// start from a missing setup. The `TempDir` must outlive the loader.
+pub fn temp_config_loader() -> (TempDir, ConfigLoader) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("themer");
+    (temp_dir, ConfigLoader { config_dir })
+}
+
+/// A config directory with empty `palettes/` and `templates/` and a default
+/// `config.toml`. Tests save their own config on top when they need targets.
+pub fn setup_config_dir() -> (TempDir, ConfigLoader) {
+    let (temp_dir, loader) = temp_config_loader();
+    fs::create_dir_all(loader.config_dir().join("palettes")).unwrap();
+    fs::create_dir_all(loader.config_dir().join("templates")).unwrap();
+    loader.save(&Config::default()).unwrap();
+    (temp_dir, loader)
+}
+
+/// Writes `palettes/<name>.json` holding [`sample_palette`] named `name`.
+pub fn write_sample_palette(loader: &ConfigLoader, name: &str) {
+    write_palette(loader, name, &sample_palette(name));
+}
+
+pub fn write_palette(loader: &ConfigLoader, name: &str, content: &str) {
+    let path = loader
+        .config_dir()
+        .join("palettes")
+        .join(format!("{}.json", name));
+    fs::write(path, content).unwrap();
+}
+
+pub fn write_template(loader: &ConfigLoader, name: &str, content: &str) {
+    fs::write(loader.config_dir().join("templates").join(name), content).unwrap();
+}
+
+/// The replace-mode target most command tests share: `app`, rendering
+/// `colors.conf` to `output`.
+pub fn app_target(output: &Path) -> Target {
+    Target {
+        name: "app".to_string(),
+        template: "colors.conf".to_string(),
+        output: output.display().to_string(),
+        mode: Mode::Replace,
+        ..Default::default()
+    }
+}
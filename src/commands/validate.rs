@@ -3,27 +3,70 @@ use std::fs;
 use std::path::Path;
 
 use crate::config::loader::ConfigLoader;
-use crate::config::models::Target;
+use crate::config::models::{Config, Target};
 use crate::output::output;
+use crate::palette::color::Color;
+use crate::palette::loader::PaletteLoader;
 use crate::palette::models::{Base16, Base30, Palette};
+use crate::target::processor::{placeholder_problems, with_target_vars};
 use crate::template::engine::TemplateEngine;
 
-pub fn execute(target_name: Option<&str>) -> Result<()> {
+/// WCAG minimum contrast ratios for normal text.
+const AA_CONTRAST: f64 = 4.5;
+const AAA_CONTRAST: f64 = 7.0;
+
+/// Foreground/background pairs audited for targets that don't declare their
+/// own: body text, status bar text and the accents on the background.
+const DEFAULT_CONTRAST_PAIRS: [(&str, &str); 10] = [
+    ("base05", "base00"),
+    ("base04", "base01"),
+    ("base08", "base00"),
+    ("base09", "base00"),
+    ("base0A", "base00"),
+    ("base0B", "base00"),
+    ("base0C", "base00"),
+    ("base0D", "base00"),
+    ("base0E", "base00"),
+    ("base0F", "base00"),
+];
+
+pub fn execute(target_name: Option<&str>, strict: bool) -> Result<()> {
     let config_loader = ConfigLoader::new()?;
+    run(&config_loader, target_name, strict)
+}
+
+/// Validates the templates of one or every target and audits their
+/// contrast. Warnings only fail validation when `strict` is set.
+fn run(config_loader: &ConfigLoader, target_name: Option<&str>, strict: bool) -> Result<()> {
     let config = config_loader.load()?;
 
-    match target_name {
-        Some(name) => validate_single_target(&config_loader, &config.targets, name),
-        None => validate_all_targets(&config_loader, &config.targets),
+    let (targets, mut warnings): (Vec<&Target>, usize) = match target_name {
+        Some(name) => {
+            let warnings = validate_single_target(config_loader, &config.targets, name)?;
+            let targets = config.targets.iter().filter(|t| t.name == name).collect();
+            (targets, warnings)
+        }
+        None => {
+            let warnings = validate_all_targets(config_loader, &config.targets)?;
+            (config.targets.iter().collect(), warnings)
+        }
+    };
+
+    warnings += audit_contrast(config_loader, &config, &targets);
+
+    if strict && warnings > 0 {
+        anyhow::bail!("{} warning(s) treated as errors (--strict)", warnings);
     }
+    Ok(())
 }
 
-fn validate_all_targets(config_loader: &ConfigLoader, targets: &[Target]) -> Result<()> {
+/// Returns the number of warnings reported.
+fn validate_all_targets(config_loader: &ConfigLoader, targets: &[Target]) -> Result<usize> {
     output::header("Validating all targets...");
 
     if targets.is_empty() {
         output::warning("No targets configured");
-        return Ok(());
+        return Ok(1);
     }
 
     let mut validation_results = Vec::new();
@@ -35,11 +78,13 @@ fn validate_all_targets(config_loader: &ConfigLoader, targets: &[Target]) -> Res
     }
 
     let mut errors = Vec::new();
+    let mut warnings = 0;
 
     for (name, result) in validation_results {
         match result {
-            Ok(()) => {
+            Ok(base30_vars) => {
                 output::item(Some("✓"), &name, Some("Valid"));
+                report_base30_dependencies(&base30_vars);
             }
             Err(e) => {
                 output::item(Some("✗"), &name, Some("Invalid"));
@@ -55,19 +100,22 @@ fn validate_all_targets(config_loader: &ConfigLoader, targets: &[Target]) -> Res
         }
     }
 
+    warnings += report_placeholder_problems(targets.iter());
+
     if errors.is_empty() {
         output::success("All templates validated successfully!");
-        Ok(())
+        Ok(warnings)
     } else {
         anyhow::bail!("{} target(s) failed validation", errors.len())
     }
 }
 
+/// Returns the number of warnings reported.
 fn validate_single_target(
     config_loader: &ConfigLoader,
     targets: &[Target],
     target_name: &str,
-) -> Result<()> {
+) -> Result<usize> {
     output::header(&format!("Validating target: {}", target_name));
 
     let target = targets
@@ -78,9 +126,12 @@ fn validate_single_target(
     let config_dir = config_loader.config_dir();
 
     match validate_target_template(config_dir, target) {
-        Ok(()) => {
+        Ok(base30_vars) => {
             output::success(&format!("Target '{}' is valid!", target_name));
-            output::item(Some("Template"), &target.template, None);
+            report_base30_dependencies(&base30_vars);
+            let warnings = report_placeholder_problems(std::iter::once(target));
+            let templates: Vec<&str> = target.template_files().collect();
+            output::item(Some("Template"), &templates.join(", "), None);
             output::item(Some("Mode"), &format!("{:?}", target.mode), None);
             output::item(
                 Some("Output"),
@@ -91,7 +142,7 @@ fn validate_single_target(
                 },
                 None,
             );
-            Ok(())
+            Ok(warnings)
         }
         Err(e) => {
             output::error(&format!("Validation failed: {}", e));
@@ -100,11 +151,34 @@ fn validate_single_target(
     }
 }
 
-fn validate_target_template(config_dir: &Path, target: &Target) -> Result<()> {
-    let template_path = config_dir.join("templates").join(&target.template);
+/// Renders each of the target's templates against the dummy palette and
+/// returns the base30 variables they reference.
+fn validate_target_template(config_dir: &Path, target: &Target) -> Result<Vec<&'static str>> {
+    let templates: Vec<&str> = target.template_files().collect();
+    if templates.is_empty() {
+        anyhow::bail!("Target '{}' has no template", target.name);
+    }
+
+    let mut base30_vars = Vec::new();
+    for template in templates {
+        for var in validate_template_file(config_dir, target, template)? {
+            if !base30_vars.contains(&var) {
+                base30_vars.push(var);
+            }
+        }
+    }
+    Ok(base30_vars)
+}
+
+fn validate_template_file(
+    config_dir: &Path,
+    target: &Target,
+    template: &str,
+) -> Result<Vec<&'static str>> {
+    let template_path = config_dir.join("templates").join(template);
 
     if !template_path.exists() {
-        anyhow::bail!("Template file not found: {}", target.template);
+        anyhow::bail!("Template file not found: {}", template);
     }
 
     let template_content = fs::read_to_string(&template_path)
@@ -113,20 +187,195 @@ fn validate_target_template(config_dir: &Path, target: &Target) -> Result<()> {
     let dummy_palette = create_dummy_palette();
 
     let mut engine = TemplateEngine::new();
+    engine.load_partials(&config_dir.join("templates"))?;
     let context = engine
-        .create_context(&dummy_palette)
+        .create_context_with(&dummy_palette, &Config::default())
         .context("Failed to create template context")?;
+    let context = with_target_vars(target, &context);
 
     engine
-        .render(&target.template, &template_content, &context)
+        .render(template, &template_content, &context)
         .with_context(|| format!("Template rendering failed for '{}'", target.name))?;
 
-    Ok(())
+    base30_dependencies(&mut engine, template, &context)
+        .with_context(|| format!("Template rendering failed for '{}'", target.name))
+}
+
+/// Re-renders an already registered template with every base30 variable
+/// removed from `context`, restoring each one the template fails on. The
+/// restored variables are the ones a base16-only palette has to derive.
+fn base30_dependencies(
+    engine: &mut TemplateEngine,
+    template_name: &str,
+    context: &tera::Context,
+) -> Result<Vec<&'static str>> {
+    let base30_keys: Vec<&'static str> = create_dummy_palette()
+        .base30()?
+        .named_colors()
+        .map(|(key, _)| key)
+        .collect();
+
+    let mut lean_context = context.clone();
+    for key in &base30_keys {
+        lean_context.remove(key);
+    }
+
+    let mut used = Vec::new();
+    loop {
+        let Err(e) = engine.render_template(template_name, &lean_context) else {
+            return Ok(used);
+        };
+
+        let missing = base30_keys.iter().find(|key| {
+            let message = format!("Variable `{}` not found", key);
+            e.chain().any(|cause| cause.to_string().contains(&message))
+        });
+
+        match missing.and_then(|key| context.get(key).map(|value| (*key, value))) {
+            Some((key, value)) if !used.contains(&key) => {
+                lean_context.insert(key, value);
+                used.push(key);
+            }
+            _ => return Err(e),
+        }
+    }
+}
+
+/// Warns about the contrast pairs of `targets` that fall below AA in the
+/// active palette and notes the ones below AAA, returning the number of
+/// warnings. Skipped when no palette has been applied yet.
+fn audit_contrast(config_loader: &ConfigLoader, config: &Config, targets: &[&Target]) -> usize {
+    if config.active_palette.is_empty() || targets.is_empty() {
+        return 0;
+    }
+
+    let palette = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)
+        .and_then(|loader| loader.load(&config.active_palette));
+    let audit = palette.and_then(|palette| audit_pairs(&palette, &contrast_pairs(targets)));
+
+    match audit {
+        Ok(audit) => {
+            for warning in &audit.warnings {
+                output::warning(warning);
+            }
+            for note in &audit.notes {
+                output::info(note);
+            }
+            audit.warnings.len()
+        }
+        Err(e) => {
+            output::warning(&format!("Skipping contrast audit: {:#}", e));
+            1
+        }
+    }
+}
+
+/// The pairs declared by `targets`, falling back to
+/// [`DEFAULT_CONTRAST_PAIRS`] for targets without any, in order and without
+/// duplicates.
+fn contrast_pairs(targets: &[&Target]) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+
+    for target in targets {
+        let declared: Vec<(String, String)> = if target.contrast.is_empty() {
+            DEFAULT_CONTRAST_PAIRS
+                .iter()
+                .map(|&(fg, bg)| (fg.to_string(), bg.to_string()))
+                .collect()
+        } else {
+            target
+                .contrast
+                .iter()
+                .map(|[fg, bg]| (fg.clone(), bg.clone()))
+                .collect()
+        };
+
+        for pair in declared {
+            if !pairs.contains(&pair) {
+                pairs.push(pair);
+            }
+        }
+    }
+
+    pairs
+}
+
+/// The outcome of a contrast audit. `warnings` (pairs below AA or naming an
+/// unknown color) count towards `--strict`; `notes` (below AAA) don't.
+#[derive(Debug, Default)]
+struct ContrastAudit {
+    warnings: Vec<String>,
+    notes: Vec<String>,
+}
+
+/// Computes the contrast ratio of each `(foreground, background)` pair of
+/// palette variables and describes the ones below AAA. Names resolve like
+/// template variables, so base30 and custom colors can be used too.
+fn audit_pairs(palette: &Palette, pairs: &[(String, String)]) -> Result<ContrastAudit> {
+    let context = TemplateEngine::new().create_context(palette)?;
+    let color = |name: &str| {
+        context
+            .get(name)
+            .and_then(|value| value.as_str())
+            .and_then(|hex| Color::from_hex(hex).ok())
+    };
+
+    let mut audit = ContrastAudit::default();
+    for (fg, bg) in pairs {
+        let (Some(fg_color), Some(bg_color)) = (color(fg), color(bg)) else {
+            audit.warnings.push(format!(
+                "Contrast pair {} on {} uses an unknown color",
+                fg, bg
+            ));
+            continue;
+        };
+
+        let ratio = fg_color.contrast(bg_color);
+        if ratio < AA_CONTRAST {
+            audit.warnings.push(format!(
+                "{} on {} has contrast {:.2}:1, below AA ({:.1})",
+                fg, bg, ratio, AA_CONTRAST
+            ));
+        } else if ratio < AAA_CONTRAST {
+            audit.notes.push(format!(
+                "{} on {} has contrast {:.2}:1, below AAA ({:.1})",
+                fg, bg, ratio, AAA_CONTRAST
+            ));
+        }
+    }
+
+    Ok(audit)
+}
+
+/// Notes the base30 variables a target uses. Informational only, since
+/// palettes without base_30 still render them from derived values.
+fn report_base30_dependencies(base30_vars: &[&str]) {
+    if base30_vars.is_empty() {
+        return;
+    }
+
+    output::info(&format!(
+        "Uses base30 variables ({}); palettes without base_30 get values derived from base16",
+        base30_vars.join(", ")
+    ));
+}
+
+/// Warns about unknown `{placeholders}` in the targets' reload_cmd and
+/// output, returning the number of warnings.
+fn report_placeholder_problems<'a>(targets: impl Iterator<Item = &'a Target>) -> usize {
+    let problems: Vec<String> = targets.flat_map(placeholder_problems).collect();
+    for problem in &problems {
+        output::warning(problem);
+    }
+    problems.len()
 }
 
 fn create_dummy_palette() -> Palette {
     Palette {
         name: "validation-dummy".to_string(),
+        base: None,
         base_16: Some(Base16 {
             base00: "000000".to_string(),
             base01: "111111".to_string(),
@@ -174,6 +423,7 @@ fn create_dummy_palette() -> Palette {
             cyan: "00aaff".to_string(),
             lightbg: "eeeeee".to_string(),
         }),
+        colors: None,
     }
 }
 
@@ -202,6 +452,7 @@ mod tests {
             output: String::new(),
             mode: Mode::Include,
             reload_cmd: String::new(),
+            ..Default::default()
         };
 
         let result = validate_target_template(loader.config_dir(), &target);
@@ -222,12 +473,55 @@ mod tests {
             output: String::new(),
             mode: Mode::Include,
             reload_cmd: String::new(),
+            ..Default::default()
         };
 
         let result = validate_target_template(loader.config_dir(), &target);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_target_template_reports_base30_dependencies() {
+        let (_temp, loader) = setup_test_env();
+        let templates_dir = loader.config_dir().join("templates");
+
+        fs::write(
+            templates_dir.join("mixed.tmpl"),
+            "bg {{ base00 }}\nerr {{ red | hex_hash }}\naccent {{ blue }}",
+        )
+        .unwrap();
+
+        let target = Target {
+            name: "test".to_string(),
+            template: "mixed.tmpl".to_string(),
+            ..Default::default()
+        };
+
+        let base30_vars = validate_target_template(loader.config_dir(), &target).unwrap();
+        assert_eq!(base30_vars, vec!["red", "blue"]);
+    }
+
+    #[test]
+    fn test_validate_target_template_base16_only() {
+        let (_temp, loader) = setup_test_env();
+        let templates_dir = loader.config_dir().join("templates");
+
+        fs::write(
+            templates_dir.join("lean.tmpl"),
+            "{% if red is defined %}{{ red }}{% endif %}{{ base08 }}",
+        )
+        .unwrap();
+
+        let target = Target {
+            name: "test".to_string(),
+            template: "lean.tmpl".to_string(),
+            ..Default::default()
+        };
+
+        let base30_vars = validate_target_template(loader.config_dir(), &target).unwrap();
+        assert!(base30_vars.is_empty());
+    }
+
     #[test]
     fn test_validate_target_template_invalid_syntax() {
         let (_temp, loader) = setup_test_env();
@@ -241,9 +535,154 @@ mod tests {
             output: String::new(),
             mode: Mode::Include,
             reload_cmd: String::new(),
+            ..Default::default()
         };
 
         let result = validate_target_template(loader.config_dir(), &target);
         assert!(result.is_err());
     }
+
+    fn save_single_target(loader: &ConfigLoader, template_content: &str) {
+        fs::write(
+            loader.config_dir().join("templates").join("app.conf"),
+            template_content,
+        )
+        .unwrap();
+        let config = Config {
+            targets: vec![Target {
+                name: "app".to_string(),
+                template: "app.conf".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        loader.save(&config).unwrap();
+    }
+
+    #[test]
+    fn test_validate_warnings_only_fail_when_strict() {
+        let (_temp, loader) = setup_test_env();
+        save_single_target(&loader, "bg = {{ base00 }}");
+        let mut config = loader.load().unwrap();
+        config.targets[0].output = "~/{app}/colors.conf".to_string();
+        loader.save(&config).unwrap();
+
+        assert!(run(&loader, None, false).is_ok());
+        assert!(run(&loader, Some("app"), false).is_ok());
+
+        let result = run(&loader, None, true);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "1 warning(s) treated as errors (--strict)"
+        );
+        assert!(run(&loader, Some("app"), true).is_err());
+    }
+
+    #[test]
+    fn test_validate_strict_passes_without_warnings() {
+        let (_temp, loader) = setup_test_env();
+        save_single_target(&loader, "bg = {{ base00 }}");
+
+        assert!(run(&loader, None, true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_strict_ignores_base30_notes() {
+        let (_temp, loader) = setup_test_env();
+        save_single_target(&loader, "accent = {{ red }}");
+
+        assert!(run(&loader, None, true).is_ok());
+        assert!(run(&loader, Some("app"), true).is_ok());
+    }
+
+    #[test]
+    fn test_validate_strict_counts_placeholder_warnings() {
+        let (_temp, loader) = setup_test_env();
+        save_single_target(&loader, "bg = {{ base00 }}");
+        let mut config = loader.load().unwrap();
+        config.targets[0].reload_cmd = "notify-send {them}".to_string();
+        loader.save(&config).unwrap();
+
+        assert!(run(&loader, Some("app"), false).is_ok());
+        assert!(run(&loader, Some("app"), true).is_err());
+    }
+
+    fn contrast_palette(foreground: &str, background: &str) -> Palette {
+        let mut base16 = Base16::filled(foreground);
+        base16.base00 = background.to_string();
+        base16.base01 = background.to_string();
+
+        Palette {
+            name: "contrast".to_string(),
+            base: None,
+            base_16: Some(base16),
+            base_30: None,
+            colors: None,
+        }
+    }
+
+    fn default_pairs() -> Vec<(String, String)> {
+        contrast_pairs(&[&Target::default()])
+    }
+
+    #[test]
+    fn test_audit_pairs_low_contrast_palette() {
+        let palette = contrast_palette("777777", "555555");
+
+        let audit = audit_pairs(&palette, &default_pairs()).unwrap();
+
+        assert_eq!(audit.warnings.len(), DEFAULT_CONTRAST_PAIRS.len());
+        assert_eq!(
+            audit.warnings[0],
+            "base05 on base00 has contrast 1.66:1, below AA (4.5)"
+        );
+    }
+
+    #[test]
+    fn test_audit_pairs_high_contrast_palette() {
+        let palette = contrast_palette("ffffff", "000000");
+
+        let audit = audit_pairs(&palette, &default_pairs()).unwrap();
+
+        assert!(audit.warnings.is_empty(), "{:?}", audit.warnings);
+        assert!(audit.notes.is_empty(), "{:?}", audit.notes);
+    }
+
+    #[test]
+    fn test_audit_pairs_notes_between_aa_and_aaa() {
+        let palette = contrast_palette("ffffff", "666666");
+
+        let audit = audit_pairs(&palette, &[("base05".to_string(), "base00".to_string())]).unwrap();
+
+        assert!(audit.warnings.is_empty());
+        assert_eq!(
+            audit.notes,
+            vec!["base05 on base00 has contrast 5.74:1, below AAA (7.0)"]
+        );
+    }
+
+    #[test]
+    fn test_contrast_pairs_prefer_declared_pairs() {
+        let declared = Target {
+            contrast: vec![["red".to_string(), "black".to_string()]],
+            ..Default::default()
+        };
+
+        let pairs = contrast_pairs(&[&declared, &Target::default(), &Target::default()]);
+
+        assert_eq!(pairs[0], ("red".to_string(), "black".to_string()));
+        assert_eq!(pairs.len(), DEFAULT_CONTRAST_PAIRS.len() + 1);
+    }
+
+    #[test]
+    fn test_audit_pairs_unknown_color() {
+        let palette = contrast_palette("ffffff", "000000");
+
+        let audit = audit_pairs(&palette, &[("base05".to_string(), "nope".to_string())]).unwrap();
+
+        assert_eq!(
+            audit.warnings,
+            vec!["Contrast pair base05 on nope uses an unknown color"]
+        );
+    }
 }
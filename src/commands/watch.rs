@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use notify::{Event, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::commands::apply::{self, ApplyOptions};
+use crate::config::loader::ConfigLoader;
+use crate::output::output;
+use crate::palette::loader::PaletteLoader;
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+enum WatchEvent {
+    Changed,
+    Shutdown,
+}
+
+pub fn execute(palette_name: &str, templates: bool) -> Result<()> {
+    let config_loader = ConfigLoader::new()?;
+    let config = config_loader.load()?;
+    let palette_path = PaletteLoader::new(config_loader.config_dir())
+        .with_aliases(&config.aliases)
+        .with_palette_dirs(&config.palette_dirs)?
+        .palette_path(palette_name);
+    let templates_dir = config_loader.config_dir().join("templates");
+
+    if !palette_path.is_file() {
+        anyhow::bail!("Palette file not found: {}", palette_path.display());
+    }
+
+    let (tx, rx) = mpsc::channel();
+
+    let shutdown_tx = tx.clone();
+    ctrlc::set_handler(move || {
+        let _ = shutdown_tx.send(WatchEvent::Shutdown);
+    })
+    .context("Failed to install Ctrl-C handler")?;
+
+    let watched_palette = palette_path.clone();
+    let watched_templates = templates.then(|| templates_dir.clone());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else { return };
+        if is_relevant(&event, &watched_palette, watched_templates.as_deref()) {
+            let _ = tx.send(WatchEvent::Changed);
+        }
+    })
+    .context("Failed to create file watcher")?;
+
+    // Watch the parent directory rather than the file itself so editors that
+    // save by replacing the file don't end the watch.
+    let palette_dir = palette_path
+        .parent()
+        .context("Palette path has no parent directory")?;
+    watcher
+        .watch(palette_dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", palette_dir.display()))?;
+
+    if templates {
+        watcher
+            .watch(&templates_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", templates_dir.display()))?;
+    }
+
+    apply::run(&config_loader, palette_name, &ApplyOptions::default())?;
+    output::info(&format!(
+        "Watching {} for changes (Ctrl-C to stop)",
+        palette_path.display()
+    ));
+
+    while wait_for_change(&rx, DEBOUNCE) {
+        output::header(&format!("Change detected, reapplying {}", palette_name));
+        if let Err(e) = apply::run(&config_loader, palette_name, &ApplyOptions::default()) {
+            output::error(&format!("{:#}", e));
+        }
+    }
+
+    output::info("Stopped watching");
+    Ok(())
+}
+
+fn is_relevant(event: &Event, palette_path: &Path, templates_dir: Option<&Path>) -> bool {
+    if event.kind.is_access() {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        path.file_name() == palette_path.file_name()
+            || templates_dir.is_some_and(|dir| path.starts_with(dir))
+    })
+}
+
+/// Blocks until a change arrives, then keeps draining events until none have
+/// arrived for `debounce`. Returns `false` once the watch should stop.
+fn wait_for_change(rx: &Receiver<WatchEvent>, debounce: Duration) -> bool {
+    match rx.recv() {
+        Ok(WatchEvent::Changed) => {}
+        Ok(WatchEvent::Shutdown) | Err(_) => return false,
+    }
+
+    loop {
+        match rx.recv_timeout(debounce) {
+            Ok(WatchEvent::Changed) => continue,
+            Ok(WatchEvent::Shutdown) | Err(RecvTimeoutError::Disconnected) => return false,
+            Err(RecvTimeoutError::Timeout) => return true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::EventKind;
+    use notify::event::{AccessKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_is_relevant_matches_palette_file() {
+        let palette = Path::new("/config/palettes/nord.json");
+        let modify = EventKind::Modify(ModifyKind::Any);
+
+        assert!(is_relevant(
+            &event(modify, "/config/palettes/nord.json"),
+            palette,
+            None
+        ));
+        assert!(!is_relevant(
+            &event(modify, "/config/palettes/gruvbox.json"),
+            palette,
+            None
+        ));
+        assert!(!is_relevant(
+            &event(
+                EventKind::Access(AccessKind::Any),
+                "/config/palettes/nord.json"
+            ),
+            palette,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_is_relevant_matches_templates_when_enabled() {
+        let palette = Path::new("/config/palettes/nord.json");
+        let templates = Path::new("/config/templates");
+        let change = event(
+            EventKind::Modify(ModifyKind::Any),
+            "/config/templates/kitty.conf",
+        );
+
+        assert!(is_relevant(&change, palette, Some(templates)));
+        assert!(!is_relevant(&change, palette, None));
+    }
+
+    #[test]
+    fn test_wait_for_change_debounces_bursts() {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..5 {
+            tx.send(WatchEvent::Changed).unwrap();
+        }
+
+        assert!(wait_for_change(&rx, Duration::from_millis(20)));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_wait_for_change_stops_on_shutdown() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(WatchEvent::Changed).unwrap();
+        tx.send(WatchEvent::Shutdown).unwrap();
+
+        assert!(!wait_for_change(&rx, Duration::from_millis(20)));
+    }
+}
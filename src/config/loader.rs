@@ -28,7 +28,20 @@ impl ConfigLoader {
         let content = fs::read_to_string(&config_path)
             .with_context(|| format!("Failed to read {}", config_path.display()))?;
 
-        toml::from_str(&content).context("Failed to parse config.toml")
+        let mut config: Config = toml::from_str(&content).context("Failed to parse config.toml")?;
+        config.migrate().context("Invalid config.toml")?;
+        config.validate().context("Invalid config.toml")?;
+        Ok(config)
+    }
+
+    /// Like [`load`](Self::load), but a missing `config.toml` gives the
+    /// default config, for commands that also work on a fresh setup.
+    pub fn load_or_default(&self) -> Result<Config> {
+        if self.config_dir.join("config.toml").exists() {
+            self.load()
+        } else {
+            Ok(Config::default())
+        }
     }
 
     pub fn save(&self, config: &Config) -> Result<()> {
@@ -44,7 +57,7 @@ impl ConfigLoader {
 
 #[cfg(test)]
 mod tests {
-    use crate::config::models::{Mode, Target};
+    use crate::config::models::{CONFIG_VERSION, Mode, Target};
 
     use super::*;
     use std::fs;
@@ -132,6 +145,26 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
         assert!(error.to_string().contains("Failed to read"));
     }
 
+    #[test]
+    fn test_load_or_default_without_config_file() {
+        let temp_dir = create_test_config_dir();
+        let loader = create_test_loader(&temp_dir);
+
+        let config = loader.load_or_default().unwrap();
+
+        assert!(config.targets.is_empty());
+        assert!(config.palette_dirs.is_empty());
+    }
+
+    #[test]
+    fn test_load_or_default_still_reports_invalid_config() {
+        let temp_dir = create_test_config_dir();
+        fs::write(temp_dir.path().join("config.toml"), "invalid toml {{").unwrap();
+        let loader = create_test_loader(&temp_dir);
+
+        assert!(loader.load_or_default().is_err());
+    }
+
     #[test]
     fn test_load_invalid_toml() {
         // Arrange
@@ -162,7 +195,9 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
                 output: "~/.config/kitty/colors.conf".to_string(),
                 mode: Mode::Replace,
                 reload_cmd: "kill -SIGUSR1 $(pgrep kitty)".to_string(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         // Act
@@ -192,6 +227,7 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
                     output: "~/.zshrc.colors".to_string(),
                     mode: Mode::Include,
                     reload_cmd: "source ~/.zshrc".to_string(),
+                    ..Default::default()
                 },
                 Target {
                     name: "tmux".to_string(),
@@ -199,8 +235,10 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
                     output: "~/.tmux.conf.colors".to_string(),
                     mode: Mode::Replace,
                     reload_cmd: "tmux source ~/.tmux.conf".to_string(),
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         // Act
@@ -220,6 +258,77 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
         );
     }
 
+    #[test]
+    fn test_load_defaults_display_settings() {
+        let temp_dir = create_test_config_dir();
+        let loader = create_test_loader(&temp_dir);
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            "active_palette = \"\"\ntargets = []\n",
+        )
+        .unwrap();
+
+        let config = loader.load().unwrap();
+
+        assert!(!config.transparency);
+        assert_eq!(config.opacity, 1.0);
+    }
+
+    #[test]
+    fn test_load_upgrades_legacy_config() {
+        let temp_dir = create_test_config_dir();
+        let loader = create_test_loader(&temp_dir);
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(&config_path, create_valid_config_content()).unwrap();
+
+        let config = loader.load().unwrap();
+
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.active_palette, "nord");
+        assert_eq!(config.opacity, 1.0);
+        assert!(config.aliases.is_empty());
+        assert!(config.targets[0].enabled);
+        assert_eq!(config.targets[0].reload_timeout, 10);
+        assert!(config.targets[0].after.is_empty());
+
+        loader.save(&config).unwrap();
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.contains(&format!("version = {}", CONFIG_VERSION)));
+    }
+
+    #[test]
+    fn test_load_rejects_newer_config_version() {
+        let temp_dir = create_test_config_dir();
+        let loader = create_test_loader(&temp_dir);
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            format!(
+                "version = {}\nactive_palette = \"\"\ntargets = []\n",
+                CONFIG_VERSION + 1
+            ),
+        )
+        .unwrap();
+
+        let err = loader.load().unwrap_err();
+
+        assert!(format!("{:#}", err).contains("only supports up to version"));
+    }
+
+    #[test]
+    fn test_load_rejects_out_of_range_opacity() {
+        let temp_dir = create_test_config_dir();
+        let loader = create_test_loader(&temp_dir);
+        fs::write(
+            temp_dir.path().join("config.toml"),
+            "active_palette = \"\"\nopacity = 1.5\ntargets = []\n",
+        )
+        .unwrap();
+
+        let err = loader.load().unwrap_err();
+
+        assert!(format!("{:#}", err).contains("opacity must be between 0.0 and 1.0, got 1.5"));
+    }
+
     #[test]
     fn test_save_empty_config() {
         // Arrange
@@ -228,6 +337,7 @@ reload_cmd = "touch ~/.config/alacritty/alacritty.toml"
         let config = Config {
             active_palette: String::new(),
             targets: vec![],
+            ..Default::default()
         };
 
         // Act
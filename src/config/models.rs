@@ -1,25 +1,198 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Schema version written to new and upgraded configs. Bump it when a
+/// change needs more than serde defaults to load old files, and add the
+/// step to [`Config::migrate`].
+pub const CONFIG_VERSION: u32 = 2;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
+    /// Schema version of the file. Configs written before the field existed
+    /// load as version 1.
+    #[serde(default = "default_version")]
+    pub version: u32,
     pub active_palette: String,
+    #[serde(default)]
+    pub previous_palette: String,
+    #[serde(default)]
+    pub post_apply_cmd: String,
+    #[serde(default)]
+    pub cache_dir: String,
+    /// Friendly names mapped to palette filenames, e.g. `mytheme = "nord"`.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Extra directories searched for palettes after `palettes/`.
+    #[serde(default)]
+    pub palette_dirs: Vec<String>,
+    /// Whether the user prefers translucent windows. Exposed to templates
+    /// as `transparency`.
+    #[serde(default)]
+    pub transparency: bool,
+    /// Window opacity from 0.0 to 1.0, exposed to templates as `opacity`.
+    #[serde(default = "default_opacity")]
+    pub opacity: f64,
     pub targets: Vec<Target>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            active_palette: String::new(),
+            previous_palette: String::new(),
+            post_apply_cmd: String::new(),
+            cache_dir: String::new(),
+            aliases: BTreeMap::new(),
+            palette_dirs: Vec::new(),
+            transparency: false,
+            opacity: default_opacity(),
+            targets: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Upgrades a config loaded from an older schema version in place, so
+    /// the next save writes [`CONFIG_VERSION`]. Returns whether anything
+    /// changed.
+    pub fn migrate(&mut self) -> anyhow::Result<bool> {
+        if self.version > CONFIG_VERSION {
+            anyhow::bail!(
+                "config.toml is version {}, but this themer only supports up to version {}",
+                self.version,
+                CONFIG_VERSION
+            );
+        }
+        if self.version == CONFIG_VERSION {
+            return Ok(false);
+        }
+
+        // Version 1 configs predate the `version` field. Every field added
+        // since has a serde default, so they only need the version bump.
+        self.version = CONFIG_VERSION;
+        Ok(true)
+    }
+
+    /// Checks settings that parse fine but make no sense.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !(0.0..=1.0).contains(&self.opacity) {
+            anyhow::bail!("opacity must be between 0.0 and 1.0, got {}", self.opacity);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Target {
     pub name: String,
+    #[serde(default)]
     pub template: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<String>,
     pub output: String,
     pub mode: Mode,
     pub reload_cmd: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backup: bool,
+    #[serde(default = "default_reload_timeout")]
+    pub reload_timeout: u64,
+    #[serde(default = "default_true")]
+    pub header: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contrast: Vec<[String; 2]>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode_bits: Option<String>,
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            template: String::new(),
+            templates: Vec::new(),
+            output: String::new(),
+            mode: Mode::default(),
+            reload_cmd: String::new(),
+            enabled: true,
+            backup: false,
+            reload_timeout: default_reload_timeout(),
+            header: true,
+            vars: BTreeMap::new(),
+            contrast: Vec::new(),
+            after: Vec::new(),
+            mode_bits: None,
+        }
+    }
+}
+
+impl Target {
+    /// The target's template files: `template`, then each of `templates`.
+    pub fn template_files(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.template.as_str())
+            .filter(|template| !template.is_empty())
+            .chain(self.templates.iter().map(String::as_str))
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+fn default_reload_timeout() -> u64 {
+    10
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+fn default_opacity() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
+    #[default]
     Include,
     Replace,
+    Symlink,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Mode::Include => "include",
+            Mode::Replace => "replace",
+            Mode::Symlink => "symlink",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Mode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "include" => Ok(Mode::Include),
+            "replace" => Ok(Mode::Replace),
+            "symlink" => Ok(Mode::Symlink),
+            other => anyhow::bail!(
+                "Unknown mode '{}', expected 'include', 'replace' or 'symlink'",
+                other
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -37,7 +210,9 @@ mod tests {
                 output: "colors.lua".to_string(),
                 mode: Mode::Replace,
                 reload_cmd: "echo 'reloaded'".to_string(),
+                ..Default::default()
             }],
+            ..Default::default()
         };
 
         // Act
@@ -49,6 +224,30 @@ mod tests {
         assert_eq!(config.targets.len(), deserialized.targets.len());
     }
 
+    #[test]
+    fn test_migrate_upgrades_older_versions() {
+        let mut config = Config {
+            version: 1,
+            ..Default::default()
+        };
+
+        assert!(config.migrate().unwrap());
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(!config.migrate().unwrap());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_versions() {
+        let mut config = Config {
+            version: CONFIG_VERSION + 1,
+            ..Default::default()
+        };
+
+        let err = config.migrate().unwrap_err();
+
+        assert!(err.to_string().contains("only supports up to version"));
+    }
+
     #[test]
     fn test_mode_serialization() {
         // Arrange
@@ -79,6 +278,28 @@ mod tests {
         assert_eq!(include, Mode::Include);
     }
 
+    #[test]
+    fn test_mode_symlink_round_trip() {
+        // Act
+        let serialized = serde_json::to_string(&Mode::Symlink).unwrap();
+        let deserialized: Mode = serde_json::from_str(&serialized).unwrap();
+
+        // Assert
+        assert_eq!(serialized, r#""symlink""#);
+        assert_eq!(deserialized, Mode::Symlink);
+        assert_eq!(Mode::Symlink.to_string(), "symlink");
+    }
+
+    #[test]
+    fn test_mode_from_str() {
+        assert_eq!("include".parse::<Mode>().unwrap(), Mode::Include);
+        assert_eq!("replace".parse::<Mode>().unwrap(), Mode::Replace);
+        assert_eq!("symlink".parse::<Mode>().unwrap(), Mode::Symlink);
+
+        let err = "overwrite".parse::<Mode>().unwrap_err();
+        assert!(err.to_string().contains("Unknown mode 'overwrite'"));
+    }
+
     #[test]
     fn test_mode_ordering() {
         // Act & Assert
@@ -92,6 +313,7 @@ mod tests {
         let config = Config {
             active_palette: String::new(),
             targets: vec![],
+            ..Default::default()
         };
 
         // Act
@@ -103,6 +325,64 @@ mod tests {
         assert_eq!(deserialized.targets.len(), 0);
     }
 
+    #[test]
+    fn test_target_enabled_defaults_to_true() {
+        // Arrange
+        let toml_str = r#"
+name = "kitty"
+template = "kitty.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+"#;
+
+        // Act
+        let target: Target = toml::from_str(toml_str).unwrap();
+
+        // Assert
+        assert!(target.enabled);
+    }
+
+    #[test]
+    fn test_target_disabled() {
+        // Arrange
+        let toml_str = r#"
+name = "kitty"
+template = "kitty.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+enabled = false
+"#;
+
+        // Act
+        let target: Target = toml::from_str(toml_str).unwrap();
+
+        // Assert
+        assert!(!target.enabled);
+    }
+
+    #[test]
+    fn test_target_reload_timeout() {
+        // Arrange
+        let default_toml = r#"
+name = "kitty"
+template = "kitty.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+"#;
+        let custom_toml = format!("{}reload_timeout = 3\n", default_toml);
+
+        // Act
+        let default_target: Target = toml::from_str(default_toml).unwrap();
+        let custom_target: Target = toml::from_str(&custom_toml).unwrap();
+
+        // Assert
+        assert_eq!(default_target.reload_timeout, 10);
+        assert_eq!(custom_target.reload_timeout, 3);
+    }
+
     #[test]
     fn test_target_with_special_characters() {
         // Arrange
@@ -112,6 +392,7 @@ mod tests {
             output: "/absolute/path/output.conf".to_string(),
             mode: Mode::Include,
             reload_cmd: "systemctl restart service && echo 'done'".to_string(),
+            ..Default::default()
         };
 
         // Act
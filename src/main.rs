@@ -1,25 +1,94 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
 mod cli;
-mod commands;
-mod config;
-mod output;
-mod palette;
-mod target;
-mod template;
 
-use cli::{Cli, Commands};
+use cli::{CacheCommand, Cli, Commands};
+use themer::commands;
+use themer::commands::apply::ApplyOptions;
+use themer::output::output;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    output::init_color(cli.no_color);
+    output::set_quiet(cli.quiet);
 
-    match cli.command {
-        Commands::List(list) => commands::list::execute(list.format.as_deref()),
+    let result = run(cli.command);
+    if cli.json_errors
+        && let Err(e) = &result
+    {
+        eprintln!("{}", output::error_json(e));
+        std::process::exit(1);
+    }
+    result
+}
+
+fn run(command: Commands) -> Result<()> {
+    match command {
+        Commands::List(list) => commands::list::execute(
+            list.format.as_deref(),
+            list.long,
+            list.sort.as_deref(),
+            list.filter.as_deref(),
+        ),
         Commands::ListTargets(list_targets) => {
             commands::list_targets::execute(list_targets.format.as_deref())
         }
-        Commands::Apply { palette } => commands::apply::execute(&palette),
-        Commands::Validate(validate) => commands::validate::execute(validate.target.as_deref()),
+        Commands::Apply(apply) => commands::apply::execute(
+            apply.palette.as_deref().unwrap_or_default(),
+            apply.name.as_deref(),
+            ApplyOptions {
+                dry_run: apply.dry_run,
+                check: apply.check,
+                parallel: apply.parallel,
+                output_dir: apply.output_dir,
+                only: apply.only,
+                except: apply.except,
+                palette_file: apply.palette_file,
+                no_reload: apply.no_reload,
+                force: apply.force,
+                ..Default::default()
+            },
+        ),
+        Commands::Validate(validate) => {
+            commands::validate::execute(validate.target.as_deref(), validate.strict)
+        }
+        Commands::Doctor(doctor) => commands::doctor::execute(doctor.fix),
+        Commands::Init(init) => commands::init::execute(init.force),
+        Commands::AddTarget(add) => commands::add_target::execute(
+            &add.name,
+            &add.template,
+            &add.output,
+            &add.mode,
+            &add.reload_cmd,
+        ),
+        Commands::RemoveTarget(remove) => commands::remove_target::execute(&remove.name),
+        Commands::Preview(preview) => commands::preview::execute(&preview.target, &preview.palette),
+        Commands::Render(render) => {
+            commands::render::execute(&render.palette, render.template_file.as_deref())
+        }
+        Commands::Diff(diff) => commands::diff::execute(&diff.palette),
+        Commands::Watch(watch) => commands::watch::execute(&watch.palette, watch.templates),
+        Commands::Generate(generate) => {
+            commands::generate::execute(&generate.from_wallpaper, &generate.name)
+        }
+        Commands::Edit(edit) => commands::edit::execute(edit.palette.as_deref()),
+        Commands::Random(random) => commands::random::execute(random.seed, random.exclude_current),
+        Commands::Revert(_) => commands::revert::execute(),
+        Commands::Export(export) => {
+            commands::export::execute(&export.palette, &export.format, export.out.as_deref())
+        }
+        Commands::Check(_) => commands::check::execute(),
+        Commands::NewPalette(new_palette) => {
+            commands::new_palette::execute(&new_palette.name, &new_palette.base, new_palette.force)
+        }
+        Commands::Cache(cache) => match cache.command {
+            CacheCommand::Clear(clear) => commands::cache::clear(clear.dry_run),
+        },
+        Commands::ScanTemplates(scan) => commands::scan_templates::execute(scan.apply),
+        Commands::Completion(completion) => {
+            commands::completion::execute(completion.shell, &mut Cli::command())
+        }
+        Commands::CompletePalettes => commands::completion::complete_palettes(),
     }
 }
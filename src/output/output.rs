@@ -1,4 +1,8 @@
 use colored::*;
+use std::cell::RefCell;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 const ICON_SUCCESS: &str = "✓";
 const ICON_ERROR: &str = "✗";
@@ -6,35 +10,185 @@ const ICON_WARNING: &str = "⚠";
 const ICON_INFO: &str = "ℹ";
 const ICON_BULLET: &str = "•";
 
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Held by tests that toggle quiet mode or assert on captured output, since
+/// quiet mode is process-wide and tests run in parallel.
+#[cfg(test)]
+pub(crate) static QUIET_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Suppresses decorative output (headers, info, items and success lines) for
+/// the rest of the process. Warnings and errors are still printed.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Turns colors off when `no_color` is set, when `NO_COLOR` is set to a
+/// non-empty value, or when stdout is not a terminal (unless
+/// `CLICOLOR_FORCE` asks for colors anyway).
+pub fn init_color(no_color: bool) {
+    let env_no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let force = env::var_os("CLICOLOR_FORCE").is_some_and(|value| value != "0");
+
+    if no_color || env_no_color || (!force && !io::stdout().is_terminal()) {
+        colored::control::set_override(false);
+    }
+}
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<CapturedLine>>> = const { RefCell::new(None) };
+}
+
+/// A line of output recorded by [`capture`] instead of being printed.
+pub struct CapturedLine {
+    text: String,
+    stderr: bool,
+}
+
+/// Runs `f`, recording everything it prints on this thread so it can be
+/// replayed later with [`replay`]. Lets worker threads keep their output
+/// together instead of interleaving it.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Vec<CapturedLine>) {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(Vec::new()));
+    let result = f();
+    let lines = CAPTURED.with(|captured| captured.borrow_mut().take().unwrap_or_default());
+    (result, lines)
+}
+
+impl CapturedLine {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+pub fn replay(lines: Vec<CapturedLine>) {
+    for line in lines {
+        emit(line.text, line.stderr);
+    }
+}
+
+fn emit(text: String, stderr: bool) {
+    let text = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+        Some(lines) => {
+            lines.push(CapturedLine { text, stderr });
+            None
+        }
+        None => Some(text),
+    });
+
+    match text {
+        Some(text) if stderr => eprintln!("{}", text),
+        Some(text) => println!("{}", text),
+        None => {}
+    }
+}
+
 pub fn header(text: &str) {
-    println!("\n{}", text.cyan().bold());
+    if is_quiet() {
+        return;
+    }
+    emit(format!("\n{}", text.cyan().bold()), false);
 }
 
 pub fn success(text: &str) {
-    println!("{} {}", ICON_SUCCESS.green().bold(), text);
+    if is_quiet() {
+        return;
+    }
+    emit(format!("{} {}", ICON_SUCCESS.green().bold(), text), false);
 }
 
 pub fn error(text: &str) {
-    eprintln!("{} {}", ICON_ERROR.red().bold(), text);
+    emit(format!("{} {}", ICON_ERROR.red().bold(), text), true);
 }
 
 pub fn warning(text: &str) {
-    println!("{} {}", ICON_WARNING.yellow().bold(), text);
+    emit(format!("{} {}", ICON_WARNING.yellow().bold(), text), false);
 }
 
 pub fn info(text: &str) {
-    println!("{} {}", ICON_INFO.blue().bold(), text);
+    if is_quiet() {
+        return;
+    }
+    emit(format!("{} {}", ICON_INFO.blue().bold(), text), false);
 }
 
 pub fn item(badge: Option<&str>, name: &str, description: Option<&str>) {
+    if is_quiet() {
+        return;
+    }
     let bullet = ICON_BULLET.dimmed();
     let badge_str = badge
         .map(|b| format!("[{}] ", b.cyan()))
         .unwrap_or_default();
     let name_str = name.green();
 
-    match description {
-        Some(desc) => println!("  {} {}{} {}", bullet, badge_str, name_str, desc.dimmed()),
-        None => println!("  {} {}{}", bullet, badge_str, name_str),
+    let line = match description {
+        Some(desc) => format!("  {} {}{} {}", bullet, badge_str, name_str, desc.dimmed()),
+        None => format!("  {} {}{}", bullet, badge_str, name_str),
+    };
+    emit(line, false);
+}
+
+/// Formats a top-level error for `--json-errors` as
+/// `{"error": "...", "chain": [...]}`, where `chain` lists the error and
+/// each of its causes, outermost first.
+pub fn error_json(err: &anyhow::Error) -> String {
+    serde_json::json!({
+        "error": err.to_string(),
+        "chain": err.chain().map(ToString::to_string).collect::<Vec<_>>(),
+    })
+    .to_string()
+}
+
+pub fn diff_line(sign: char, text: &str) {
+    let line = format!("{}{}", sign, text);
+    let line = match sign {
+        '+' => line.green(),
+        '-' => line.red(),
+        _ => line.dimmed(),
+    };
+    emit(format!("    {}", line), false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_json_lists_chain() {
+        let err = Err::<(), _>(anyhow::anyhow!("No such file"))
+            .context("Failed to read config.toml")
+            .unwrap_err();
+
+        let json: serde_json::Value = serde_json::from_str(&error_json(&err)).unwrap();
+
+        assert_eq!(json["error"], "Failed to read config.toml");
+        assert_eq!(
+            json["chain"],
+            serde_json::json!(["Failed to read config.toml", "No such file"])
+        );
+    }
+
+    #[test]
+    fn test_quiet_suppresses_info_but_not_error() {
+        let _lock = QUIET_TEST_LOCK.lock().unwrap();
+        set_quiet(true);
+        let ((), lines) = capture(|| {
+            header("Header");
+            info("Info");
+            success("Success");
+            item(None, "item", None);
+            error("Error");
+        });
+        set_quiet(false);
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].stderr);
+        assert!(lines[0].text.contains("Error"));
     }
 }
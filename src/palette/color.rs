@@ -0,0 +1,388 @@
+use super::models::ColorError;
+
+/// An sRGB color with 8-bit channels. Every color conversion themer does
+/// (filters, wallpaper extraction) goes through this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0);
+    pub const WHITE: Color = Color::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parses a 6-digit hex color, with or without a leading '#'.
+    pub fn from_hex(hex: &str) -> Result<Self, ColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ColorError::InvalidFormat(hex.to_string()));
+        }
+
+        let channel = |range| u8::from_str_radix(&digits[range], 16);
+        match (channel(0..2), channel(2..4), channel(4..6)) {
+            (Ok(r), Ok(g), Ok(b)) => Ok(Self::new(r, g, b)),
+            _ => Err(ColorError::InvalidFormat(hex.to_string())),
+        }
+    }
+
+    /// Lowercase 6-digit hex without a '#', the format palettes use.
+    pub fn to_hex(self) -> String {
+        format!("{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Converts to HSL, with hue in degrees [0, 360) and saturation and
+    /// lightness in [0.0, 1.0].
+    pub fn to_hsl(self) -> (f64, f64, f64) {
+        let [r, g, b] = self.unit_channels();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let l = (max + min) / 2.0;
+
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        (hue(r, g, b, max, delta), s, l)
+    }
+
+    /// Converts from HSL (hue in degrees, saturation and lightness in
+    /// [0.0, 1.0]). The hue wraps around.
+    pub fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Self::from_chroma(h, chroma, l - chroma / 2.0)
+    }
+
+    /// Converts to HSV, with hue in degrees [0, 360) and saturation and
+    /// value in [0.0, 1.0].
+    pub fn to_hsv(self) -> (f64, f64, f64) {
+        let [r, g, b] = self.unit_channels();
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        if delta == 0.0 {
+            return (0.0, 0.0, max);
+        }
+
+        (hue(r, g, b, max, delta), delta / max, max)
+    }
+
+    /// Converts from HSV (hue in degrees, saturation and value in
+    /// [0.0, 1.0]). The hue wraps around.
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> Self {
+        let chroma = v * s;
+        Self::from_chroma(h, chroma, v - chroma)
+    }
+
+    /// WCAG 2.x relative luminance, in [0.0, 1.0].
+    pub fn luminance(self) -> f64 {
+        let [r, g, b] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// WCAG contrast ratio against another color, in [1.0, 21.0].
+    pub fn contrast(self, other: Color) -> f64 {
+        contrast_ratio(self.luminance(), other.luminance())
+    }
+
+    /// Linearly interpolates toward `other` in RGB. `amount` 0.0 gives
+    /// `self` and 1.0 gives `other`.
+    pub fn mix(self, other: Color, amount: f64) -> Self {
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * amount).round() as u8;
+        Self::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+
+    /// Rotates the hue in HSL, keeping saturation and lightness.
+    pub fn rotate_hue(self, degrees: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self::from_hsl(h + degrees, s, l)
+    }
+
+    /// Channels decoded to linear light in [0.0, 1.0].
+    pub fn to_linear(self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(srgb_to_linear)
+    }
+
+    /// Inverse of [`to_linear`](Self::to_linear), clamping out-of-gamut
+    /// values.
+    pub fn from_linear([r, g, b]: [f64; 3]) -> Self {
+        Self::new(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b))
+    }
+
+    /// CIE L*a*b* coordinates under the D65 white point.
+    pub fn to_lab(self) -> [f64; 3] {
+        let [r, g, b] = self.to_linear();
+        let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+        let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+        let z = (0.0193339 * r + 0.1191920 * g + 0.9503041 * b) / 1.08883;
+
+        let [fx, fy, fz] = [x, y, z].map(lab_f);
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+
+    /// Perceptual difference to another color (CIE76, Euclidean distance in
+    /// L*a*b*). Around 2.3 is the smallest difference most people notice.
+    pub fn distance(self, other: Color) -> f64 {
+        let [l1, a1, b1] = self.to_lab();
+        let [l2, a2, b2] = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    fn unit_channels(self) -> [f64; 3] {
+        [self.r, self.g, self.b].map(|c| f64::from(c) / 255.0)
+    }
+
+    /// Shared tail of the HSL and HSV conversions: places `chroma` on the
+    /// hue wheel and adds `m` to every channel.
+    fn from_chroma(h: f64, chroma: f64, m: f64) -> Self {
+        let h_prime = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h_prime.rem_euclid(2.0) - 1.0).abs());
+
+        let (r, g, b) = match h_prime as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let to_channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+        Self::new(to_channel(r), to_channel(g), to_channel(b))
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::new(r, g, b)
+    }
+}
+
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> Self {
+        (color.r, color.g, color.b)
+    }
+}
+
+/// WCAG contrast ratio between two relative luminances, in [1.0, 21.0].
+pub fn contrast_ratio(a: f64, b: f64) -> f64 {
+    let (lighter, darker) = if a >= b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Hue in degrees shared by HSL and HSV, from unit channels, their maximum
+/// and the chroma (`max - min`, non-zero).
+fn hue(r: f64, g: f64, b: f64, max: f64, delta: f64) -> f64 {
+    if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    }
+}
+
+/// Converts a gamma-encoded sRGB channel to linear light in [0.0, 1.0].
+fn srgb_to_linear(channel: u8) -> f64 {
+    let c = f64::from(channel) / 255.0;
+    if c <= 0.03928 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The CIE L*a*b* companding function, linear near zero.
+fn lab_f(t: f64) -> f64 {
+    const DELTA: f64 = 6.0 / 29.0;
+    if t > DELTA.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+    }
+}
+
+/// Inverse of [`srgb_to_linear`], clamping out-of-gamut values.
+fn linear_to_srgb(value: f64) -> u8 {
+    let value = value.clamp(0.0, 1.0);
+    let c = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gruvbox dark base16, covering grays, saturated accents and both
+    /// extremes of lightness.
+    const BASE16_SAMPLES: [&str; 16] = [
+        "1d2021", "3c3836", "504945", "665c54", "bdae93", "d5c4a1", "ebdbb2", "fbf1c7", "fb4934",
+        "fe8019", "fabd2f", "b8bb26", "8ec07c", "83a598", "d3869b", "d65d0e",
+    ];
+
+    fn hex(value: &str) -> Color {
+        Color::from_hex(value).unwrap()
+    }
+
+    fn assert_close(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        let close = |a: f64, b: f64| (a - b).abs() < 0.005;
+        assert!(
+            close(actual.0, expected.0)
+                && close(actual.1, expected.1)
+                && close(actual.2, expected.2),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_from_hex_and_to_hex() {
+        assert_eq!(hex("ff8000"), Color::new(255, 128, 0));
+        assert_eq!(hex("#FF8000"), Color::new(255, 128, 0));
+        assert_eq!(Color::new(255, 128, 0).to_hex(), "ff8000");
+    }
+
+    #[test]
+    fn test_from_hex_rejects_invalid() {
+        for invalid in [
+            "", "fff", "#12345", "1234567", "gg0000", "#ff00zz", "ff 000",
+        ] {
+            assert!(Color::from_hex(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_to_hsl_known_values() {
+        assert_close(hex("ff0000").to_hsl(), (0.0, 1.0, 0.5));
+        assert_close(hex("00ff00").to_hsl(), (120.0, 1.0, 0.5));
+        assert_close(hex("0000ff").to_hsl(), (240.0, 1.0, 0.5));
+        assert_close(hex("ffffff").to_hsl(), (0.0, 0.0, 1.0));
+        assert_close(hex("000000").to_hsl(), (0.0, 0.0, 0.0));
+        assert_close(hex("808080").to_hsl(), (0.0, 0.0, 128.0 / 255.0));
+        assert_close(hex("ff00ff").to_hsl(), (300.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_from_hsl_known_values() {
+        assert_eq!(Color::from_hsl(0.0, 1.0, 0.5), hex("ff0000"));
+        assert_eq!(Color::from_hsl(120.0, 1.0, 0.5), hex("00ff00"));
+        assert_eq!(Color::from_hsl(240.0, 1.0, 0.25), hex("000080"));
+        assert_eq!(Color::from_hsl(60.0, 1.0, 0.75), hex("ffff80"));
+        assert_eq!(Color::from_hsl(-120.0, 1.0, 0.5), hex("0000ff"));
+        assert_eq!(Color::from_hsl(480.0, 1.0, 0.5), hex("00ff00"));
+    }
+
+    #[test]
+    fn test_to_hsv_known_values() {
+        assert_close(hex("ff0000").to_hsv(), (0.0, 1.0, 1.0));
+        assert_close(hex("00ff00").to_hsv(), (120.0, 1.0, 1.0));
+        assert_close(hex("000080").to_hsv(), (240.0, 1.0, 128.0 / 255.0));
+        assert_close(hex("ffffff").to_hsv(), (0.0, 0.0, 1.0));
+        assert_close(hex("000000").to_hsv(), (0.0, 0.0, 0.0));
+        assert_close(hex("ff8080").to_hsv(), (0.0, 127.0 / 255.0, 1.0));
+    }
+
+    #[test]
+    fn test_from_hsv_known_values() {
+        assert_eq!(Color::from_hsv(0.0, 1.0, 1.0), hex("ff0000"));
+        assert_eq!(Color::from_hsv(180.0, 1.0, 1.0), hex("00ffff"));
+        assert_eq!(Color::from_hsv(300.0, 0.5, 1.0), hex("ff80ff"));
+        assert_eq!(Color::from_hsv(42.0, 0.0, 0.5), hex("808080"));
+        assert_eq!(Color::from_hsv(0.0, 0.0, 0.0), hex("000000"));
+    }
+
+    #[test]
+    fn test_hsl_round_trips_base16_samples() {
+        for sample in BASE16_SAMPLES {
+            let color = hex(sample);
+            let (h, s, l) = color.to_hsl();
+            assert_eq!(Color::from_hsl(h, s, l), color, "{}", sample);
+        }
+    }
+
+    #[test]
+    fn test_hsv_round_trips_base16_samples() {
+        for sample in BASE16_SAMPLES {
+            let color = hex(sample);
+            let (h, s, v) = color.to_hsv();
+            assert_eq!(Color::from_hsv(h, s, v), color, "{}", sample);
+        }
+    }
+
+    #[test]
+    fn test_hex_and_linear_round_trip_base16_samples() {
+        for sample in BASE16_SAMPLES {
+            let color = hex(sample);
+            assert_eq!(color.to_hex(), sample);
+            assert_eq!(Color::from_linear(color.to_linear()), color, "{}", sample);
+        }
+    }
+
+    #[test]
+    fn test_hsl_and_hsv_agree_on_hue() {
+        for sample in BASE16_SAMPLES {
+            let color = hex(sample);
+            assert!(
+                (color.to_hsl().0 - color.to_hsv().0).abs() < 1e-9,
+                "{}",
+                sample
+            );
+        }
+    }
+
+    #[test]
+    fn test_luminance_and_contrast() {
+        assert_eq!(Color::BLACK.luminance(), 0.0);
+        assert!((Color::WHITE.luminance() - 1.0).abs() < 1e-9);
+        assert!((hex("808080").luminance() - 0.2158605).abs() < 1e-6);
+        assert!((Color::BLACK.contrast(Color::WHITE) - 21.0).abs() < 1e-9);
+        assert_eq!(hex("777777").contrast(hex("777777")), 1.0);
+    }
+
+    #[test]
+    fn test_mix_and_rotate_hue() {
+        assert_eq!(Color::BLACK.mix(Color::WHITE, 0.5), hex("808080"));
+        assert_eq!(hex("ff0000").mix(hex("0000ff"), 0.0), hex("ff0000"));
+        assert_eq!(hex("ff0000").mix(hex("0000ff"), 1.0), hex("0000ff"));
+        assert_eq!(hex("ff0000").rotate_hue(120.0), hex("00ff00"));
+        assert_eq!(hex("ff0000").rotate_hue(-120.0), hex("0000ff"));
+    }
+
+    #[test]
+    fn test_lab_and_distance() {
+        let [l, a, b] = Color::WHITE.to_lab();
+        assert!((l - 100.0).abs() < 0.01 && a.abs() < 0.01 && b.abs() < 0.01);
+        assert_eq!(Color::BLACK.to_lab()[0], 0.0);
+
+        let [l, a, b] = hex("ff0000").to_lab();
+        assert!((l - 53.24).abs() < 0.1 && (a - 80.09).abs() < 0.1 && (b - 67.20).abs() < 0.1);
+
+        assert_eq!(hex("336699").distance(hex("336699")), 0.0);
+        assert!(hex("fb4934").distance(hex("fa4a35")) < 1.0);
+        assert!(hex("ff0000").distance(hex("ff2000")) < hex("ff0000").distance(hex("00ff00")));
+    }
+
+    #[test]
+    fn test_tuple_conversions() {
+        let color = Color::from((1, 2, 3));
+        assert_eq!(color, Color::new(1, 2, 3));
+        assert_eq!(<(u8, u8, u8)>::from(color), (1, 2, 3));
+    }
+}
@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use image::imageops::FilterType;
+use std::path::Path;
+
+use super::color::Color;
+use super::models::{Base16, Palette};
+
+type Rgb = (u8, u8, u8);
+
+/// Wallpapers are downscaled to at most this many pixels per side before
+/// quantizing, which keeps extraction fast without changing the result much.
+const SAMPLE_SIZE: u32 = 128;
+const QUANTIZED_COLORS: usize = 16;
+const ACCENT_COUNT: usize = 8;
+
+/// Position of base00..base07 along the background → foreground ramp.
+const RAMP: [f64; 8] = [0.0, 0.08, 0.16, 0.3, 0.55, 0.75, 0.88, 1.0];
+
+/// Builds a palette from the dominant colors of an image.
+///
+/// The most common color becomes the background (base00) and base01..base07
+/// ramp from it towards a contrasting foreground. The accents (base08..base0F)
+/// are the remaining saturated colors ordered by hue, topped up with hue
+/// rotations when the image doesn't have enough distinct colors. Base30 is
+/// left out and derived from base16 when the palette is used.
+pub fn palette_from_image(path: &Path, name: &str) -> Result<Palette> {
+    let image = image::open(path)
+        .with_context(|| format!("Failed to read image: {}", path.display()))?
+        .resize(SAMPLE_SIZE, SAMPLE_SIZE, FilterType::Triangle)
+        .to_rgb8();
+
+    let pixels: Vec<Rgb> = image.pixels().map(|p| (p[0], p[1], p[2])).collect();
+    if pixels.is_empty() {
+        anyhow::bail!("Image has no pixels: {}", path.display());
+    }
+
+    Ok(Palette {
+        name: name.to_string(),
+        base: None,
+        base_16: Some(base16_from_colors(&median_cut(pixels, QUANTIZED_COLORS))),
+        base_30: None,
+        colors: None,
+    })
+}
+
+/// Quantizes `pixels` into at most `count` colors with the median cut
+/// algorithm. Returns each color with the number of pixels it represents,
+/// most common first.
+fn median_cut(pixels: Vec<Rgb>, count: usize) -> Vec<(Rgb, usize)> {
+    let mut buckets = vec![pixels];
+
+    while buckets.len() < count {
+        let Some((index, channel)) = widest_bucket(&buckets) else {
+            break;
+        };
+
+        let mut bucket = buckets.swap_remove(index);
+        bucket.sort_unstable_by_key(|&pixel| channel_value(pixel, channel));
+        let upper = bucket.split_off(bucket.len() / 2);
+        buckets.push(bucket);
+        buckets.push(upper);
+    }
+
+    let mut colors: Vec<(Rgb, usize)> = buckets
+        .iter()
+        .map(|bucket| (average(bucket), bucket.len()))
+        .collect();
+    colors.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    colors
+}
+
+/// Finds the bucket with the largest spread in any channel, along with that
+/// channel. Returns `None` once every bucket holds a single color.
+fn widest_bucket(buckets: &[Vec<Rgb>]) -> Option<(usize, usize)> {
+    buckets
+        .iter()
+        .enumerate()
+        .filter(|(_, bucket)| bucket.len() > 1)
+        .flat_map(|(index, bucket)| {
+            (0..3).map(move |channel| {
+                let values = bucket.iter().map(|&pixel| channel_value(pixel, channel));
+                let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                (index, channel, range)
+            })
+        })
+        .filter(|&(_, _, range)| range > 0)
+        .max_by_key(|&(_, _, range)| range)
+        .map(|(index, channel, _)| (index, channel))
+}
+
+fn channel_value((r, g, b): Rgb, channel: usize) -> u8 {
+    match channel {
+        0 => r,
+        1 => g,
+        _ => b,
+    }
+}
+
+fn average(pixels: &[Rgb]) -> Rgb {
+    let len = pixels.len().max(1) as u64;
+    let (r, g, b) = pixels.iter().fold((0u64, 0u64, 0u64), |(r, g, b), p| {
+        (r + u64::from(p.0), g + u64::from(p.1), b + u64::from(p.2))
+    });
+    ((r / len) as u8, (g / len) as u8, (b / len) as u8)
+}
+
+fn base16_from_colors(colors: &[(Rgb, usize)]) -> Base16 {
+    let background = colors
+        .first()
+        .map_or(Color::BLACK, |&(color, _)| Color::from(color));
+    let is_dark = background.luminance() < 0.18;
+    let foreground = if is_dark {
+        background.mix(Color::WHITE, 0.85)
+    } else {
+        background.mix(Color::BLACK, 0.85)
+    };
+
+    let ramp = RAMP.map(|amount| background.mix(foreground, amount));
+    let colors: Vec<String> = ramp
+        .into_iter()
+        .chain(accents(colors, is_dark))
+        .map(Color::to_hex)
+        .collect();
+
+    Base16::from_colors(
+        colors
+            .try_into()
+            .expect("8 ramp colors and 8 accents make 16"),
+    )
+}
+
+fn accents(colors: &[(Rgb, usize)], is_dark: bool) -> [Color; ACCENT_COUNT] {
+    let mut candidates: Vec<(f64, f64, f64)> = colors
+        .iter()
+        .skip(1)
+        .map(|&(color, _)| Color::from(color).to_hsl())
+        .filter(|&(_, s, l)| s >= 0.2 && (0.15..=0.85).contains(&l))
+        .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    candidates.dedup_by(|a, b| (a.0 - b.0).abs() < 10.0);
+    candidates.truncate(ACCENT_COUNT);
+
+    // Synthesize the missing accents by rotating a seed hue around the color
+    // wheel, keeping them readable on the background.
+    let seed_hue = candidates.first().map(|&(h, _, _)| h).unwrap_or_else(|| {
+        colors
+            .first()
+            .map_or(0.0, |&(color, _)| Color::from(color).to_hsl().0)
+    });
+    let lightness = if is_dark { 0.65 } else { 0.45 };
+    let step = 360.0 / ACCENT_COUNT as f64;
+
+    let mut accents = candidates;
+    let mut k = 1;
+    while accents.len() < ACCENT_COUNT {
+        accents.push((seed_hue + step * f64::from(k), 0.6, lightness));
+        k += 1;
+    }
+
+    let mut result = [Color::BLACK; ACCENT_COUNT];
+    for (slot, (h, s, l)) in result.iter_mut().zip(accents) {
+        *slot = Color::from_hsl(h, s, l);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb as Pixel, RgbImage};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_palette_from_solid_image() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("solid.png");
+        RgbImage::from_pixel(8, 8, Pixel([0x33, 0x66, 0x99]))
+            .save(&path)
+            .unwrap();
+
+        let palette = palette_from_image(&path, "solid").unwrap();
+        let base16 = palette.base16().unwrap();
+
+        assert_eq!(palette.name, "solid");
+        assert_eq!(base16.base00, "336699");
+        assert!(palette.validate().is_ok());
+    }
+
+    #[test]
+    fn test_palette_from_missing_image() {
+        let result = palette_from_image(Path::new("/nonexistent/wallpaper.png"), "x");
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to read image")
+        );
+    }
+
+    #[test]
+    fn test_median_cut_separates_distinct_colors() {
+        let mut pixels = vec![(255, 0, 0); 30];
+        pixels.extend(vec![(0, 0, 255); 10]);
+
+        let colors = median_cut(pixels, 4);
+
+        assert_eq!(colors[0], ((255, 0, 0), 20));
+        assert!(colors.iter().any(|&(color, _)| color == (0, 0, 255)));
+    }
+
+    #[test]
+    fn test_median_cut_single_color() {
+        let colors = median_cut(vec![(10, 20, 30); 16], 16);
+
+        assert_eq!(colors, vec![((10, 20, 30), 16)]);
+    }
+}
@@ -1,63 +1,154 @@
 use anyhow::{Context, Result};
-use serde::Serialize;
-use std::borrow::Cow;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::models::Palette;
+use crate::target::processor::expand_path;
+
+/// Supported palette file extensions, in lookup order for extensionless names.
+const PALETTE_EXTENSIONS: [&str; 3] = ["json", "yaml", "yml"];
 
 pub struct PaletteLoader {
     palettes_dir: PathBuf,
+    /// Extra directories from `palette_dirs`, searched after `palettes_dir`.
+    extra_dirs: Vec<PathBuf>,
+    aliases: BTreeMap<String, String>,
 }
 
 impl PaletteLoader {
     pub fn new(config_dir: &Path) -> Self {
         Self {
             palettes_dir: config_dir.join("palettes"),
+            extra_dirs: Vec::new(),
+            aliases: BTreeMap::new(),
         }
     }
 
-    pub fn load(&self, palette_name: &str) -> Result<Palette> {
-        let palette_file: Cow<str> = if palette_name.ends_with(".json") {
-            Cow::Borrowed(palette_name)
+    /// Also searches `palette_dirs` (after the default palettes directory)
+    /// when loading and listing. `~` and `$VAR` are expanded.
+    pub fn with_palette_dirs(mut self, palette_dirs: &[String]) -> Result<Self> {
+        self.extra_dirs = palette_dirs
+            .iter()
+            .map(|dir| expand_path(dir))
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Resolves names through `aliases` (alias → palette filename) before
+    /// looking them up. Names that aren't aliases are used as-is.
+    pub fn with_aliases(mut self, aliases: &BTreeMap<String, String>) -> Self {
+        self.aliases = aliases.clone();
+        self
+    }
+
+    pub fn resolve<'a>(&'a self, palette_name: &'a str) -> &'a str {
+        self.aliases
+            .get(palette_name)
+            .map_or(palette_name, String::as_str)
+    }
+
+    /// Finds the palette file, looking through the default directory first
+    /// and then each of `palette_dirs`. Falls back to a path in the default
+    /// directory when no file exists.
+    pub fn palette_path(&self, palette_name: &str) -> PathBuf {
+        let palette_name = self.resolve(palette_name);
+        let filenames: Vec<String> = if has_palette_extension(Path::new(palette_name)) {
+            vec![palette_name.to_string()]
         } else {
-            Cow::Owned(format!("{}.json", palette_name))
+            PALETTE_EXTENSIONS
+                .iter()
+                .map(|ext| format!("{}.{}", palette_name, ext))
+                .collect()
         };
 
-        let palette_path = self.palettes_dir.join(palette_file.as_ref());
-        let content = fs::read_to_string(&palette_path)
-            .with_context(|| format!("Failed to read palette: {}", palette_path.display()))?;
+        std::iter::once(&self.palettes_dir)
+            .chain(&self.extra_dirs)
+            .flat_map(|dir| filenames.iter().map(move |filename| dir.join(filename)))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| self.palettes_dir.join(&filenames[0]))
+    }
 
-        let palette: Palette =
-            serde_json::from_str(&content).context("Failed to parse palette JSON")?;
+    pub fn exists(&self, palette_name: &str) -> bool {
+        self.palette_path(palette_name).is_file()
+    }
 
-        Ok(palette)
+    /// Loads a palette, following its `base` chain: each palette inherits
+    /// every color it doesn't set itself from its parent.
+    pub fn load(&self, palette_name: &str) -> Result<Palette> {
+        let path = self.palette_path(palette_name);
+        let value = self.load_inherited(palette_name, &mut Vec::new())?;
+        Palette::from_value(value, &path)
     }
 
+    /// Reads a palette as raw data and overlays it on its parent's, if any.
+    /// `chain` holds the palettes already visited, to detect cycles.
+    fn load_inherited(&self, palette_name: &str, chain: &mut Vec<String>) -> Result<Value> {
+        let resolved = self.resolve(palette_name).to_string();
+        if chain.contains(&resolved) {
+            anyhow::bail!(
+                "Palette inheritance cycle: {} -> {}",
+                chain.join(" -> "),
+                resolved
+            );
+        }
+        chain.push(resolved);
+
+        let mut value = read_palette_value(&self.palette_path(palette_name))?;
+
+        if let Some(parent) = value.get("base").and_then(Value::as_str) {
+            let parent = parent.to_string();
+            let mut merged = self
+                .load_inherited(&parent, chain)
+                .with_context(|| format!("Failed to load base palette '{}'", parent))?;
+            overlay(&mut merged, value);
+            value = merged;
+        }
+
+        Ok(value)
+    }
+
+    /// Writes `palette` as pretty JSON to `palettes/<name>.json`, replacing
+    /// any existing file. Nested names like `dark/nord` create the
+    /// subdirectory.
+    pub fn save(&self, palette: &Palette) -> Result<PathBuf> {
+        let path = self.palettes_dir.join(format!("{}.json", palette.name));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(palette).context("Failed to serialize palette")?;
+        fs::write(&path, format!("{}\n", content))
+            .with_context(|| format!("Failed to write palette: {}", path.display()))?;
+
+        Ok(path)
+    }
+
+    /// Lists every palette file under the palettes directory, including
+    /// subdirectories. Nested palettes are named by their path relative to
+    /// the palettes directory, e.g. `dark/nord`.
+    ///
+    /// Palettes from `palette_dirs` follow, skipping missing directories and
+    /// any filename already listed, since that one is what `load` finds.
     pub fn list_all(&self) -> Result<Vec<PaletteInfo>> {
-        let entries = fs::read_dir(&self.palettes_dir).with_context(|| {
-            format!("Failed to read directory: {}", self.palettes_dir.display())
-        })?;
-
-        let palettes: Vec<PaletteInfo> = entries
-            .filter_map(|entry| {
-                let entry = entry.ok()?;
-                let path = entry.path();
-
-                if path.extension().and_then(|s| s.to_str()) == Some("json")
-                    && let Some(filename) = path.file_stem().and_then(|s| s.to_str())
-                {
-                    let name = extract_palette_name(&path).ok();
-
-                    Some(PaletteInfo {
-                        filename: filename.to_string(),
-                        name,
-                    })
-                } else {
-                    None
+        let mut palettes = Vec::new();
+        collect_palettes(&self.palettes_dir, "", &mut palettes)?;
+
+        for dir in self.extra_dirs.iter().filter(|dir| dir.is_dir()) {
+            let mut extra = Vec::new();
+            collect_palettes(dir, "", &mut extra)?;
+            for palette in extra {
+                if !palettes.iter().any(|p| p.filename == palette.filename) {
+                    palettes.push(palette);
                 }
-            })
-            .collect();
+            }
+        }
 
         Ok(palettes)
     }
@@ -87,25 +178,146 @@ impl PaletteLoader {
     }
 }
 
+impl Palette {
+    /// Reads, normalizes and validates a palette file at any path. The format
+    /// (JSON, YAML or a base16 scheme) is picked from the extension and
+    /// content.
+    ///
+    /// A `base` can't be resolved without a palettes directory, so files
+    /// loaded this way must set every color themselves.
+    pub fn from_path(path: &Path) -> Result<Palette> {
+        let value = read_palette_value(path)?;
+        if value.get("base").is_some() {
+            anyhow::bail!(
+                "Palette {} sets 'base', which is only supported for palettes in the palettes directory",
+                path.display()
+            );
+        }
+
+        Palette::from_value(value, path)
+    }
+
+    fn from_value(value: Value, path: &Path) -> Result<Palette> {
+        let mut palette: Palette = serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse palette: {}", path.display()))?;
+
+        palette
+            .normalize()
+            .with_context(|| format!("Invalid palette: {}", path.display()))?;
+        palette
+            .validate()
+            .with_context(|| format!("Invalid palette: {}", path.display()))?;
+
+        Ok(palette)
+    }
+}
+
+/// Reads a palette file as untyped data so it can be merged with its parent
+/// before being checked for missing fields.
+fn read_palette_value(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read palette: {}", path.display()))?;
+
+    if is_yaml(path) && is_base16_scheme(&content) {
+        let palette =
+            Palette::from_base16_scheme(&content).context("Failed to parse base16 scheme")?;
+        return Ok(serde_json::to_value(palette)?);
+    }
+
+    deserialize_palette_file(path, &content, "palette")
+}
+
+/// Recursively copies `child` over `parent`: objects are merged key by key,
+/// anything else in the child replaces the parent's value.
+fn overlay(parent: &mut Value, child: Value) {
+    match (parent, child) {
+        (Value::Object(parent), Value::Object(child)) => {
+            for (key, value) in child {
+                match parent.get_mut(&key) {
+                    Some(existing) => overlay(existing, value),
+                    None => {
+                        parent.insert(key, value);
+                    }
+                }
+            }
+        }
+        (parent, child) => *parent = child,
+    }
+}
+
+fn collect_palettes(dir: &Path, prefix: &str, palettes: &mut Vec<PaletteInfo>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+
+        if is_dir && let Some(dirname) = path.file_name().and_then(|s| s.to_str()) {
+            collect_palettes(&path, &format!("{}{}/", prefix, dirname), palettes)?;
+        } else if has_palette_extension(&path)
+            && let Some(filename) = path.file_stem().and_then(|s| s.to_str())
+        {
+            palettes.push(PaletteInfo {
+                filename: format!("{}{}", prefix, filename),
+                name: extract_palette_name(&path).ok(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 fn extract_palette_name(path: &Path) -> Result<String> {
     use serde::Deserialize;
 
     #[derive(Deserialize)]
     struct NameOnly {
+        #[serde(alias = "scheme")]
         name: String,
     }
 
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
-    let name_only: NameOnly =
-        serde_json::from_str(&content).context("Failed to parse name from palette JSON")?;
+    let name_only: NameOnly = deserialize_palette_file(path, &content, "name from palette")?;
 
     Ok(name_only.name)
 }
 
+fn has_palette_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| PALETTE_EXTENSIONS.contains(&ext))
+}
+
+fn is_yaml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|s| s.to_str()),
+        Some("yaml" | "yml")
+    )
+}
 
-#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+/// Whether a YAML document uses the base16 scheme layout (`scheme` instead of `name`).
+fn is_base16_scheme(content: &str) -> bool {
+    serde_yaml::from_str::<serde_yaml::Mapping>(content)
+        .is_ok_and(|mapping| mapping.contains_key("scheme") && !mapping.contains_key("name"))
+}
+
+/// Deserializes palette data as YAML or JSON depending on the file extension.
+fn deserialize_palette_file<T: DeserializeOwned>(
+    path: &Path,
+    content: &str,
+    what: &str,
+) -> Result<T> {
+    if is_yaml(path) {
+        serde_yaml::from_str(content).with_context(|| format!("Failed to parse {} YAML", what))
+    } else {
+        serde_json::from_str(content).with_context(|| format!("Failed to parse {} JSON", what))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct PaletteInfo {
     pub filename: String,
     pub name: Option<String>,
@@ -169,6 +381,32 @@ mod tests {
         assert_eq!(palette.name, "Test Palette");
     }
 
+    #[test]
+    fn test_load_keeps_custom_colors() {
+        let (_temp_dir, loader) = setup_test_palettes();
+        let palette = loader.load("test").unwrap();
+        let colors = palette.colors.unwrap();
+
+        assert_eq!(colors["primary"], "ffffff");
+        assert_eq!(colors["secondary"], "000000");
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_custom_color() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(
+            palettes_dir.join("bad.json"),
+            r#"{"name": "Bad", "colors": {"accent": "nothex"}}"#,
+        )
+        .unwrap();
+
+        let err = PaletteLoader::new(temp_dir.path()).load("bad").unwrap_err();
+
+        assert!(format!("{:#}", err).contains("colors.accent = 'nothex'"));
+    }
+
     #[test]
     fn test_load_with_json_extension() {
         let (_temp_dir, loader) = setup_test_palettes();
@@ -202,6 +440,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exists() {
+        let (_temp_dir, loader) = setup_test_palettes();
+        assert!(loader.exists("test"));
+        assert!(loader.exists("test.json"));
+        assert!(!loader.exists("nonexistent"));
+    }
+
     #[test]
     fn test_list_all_with_palettes() {
         let (_temp_dir, loader) = setup_test_palettes();
@@ -220,6 +466,62 @@ mod tests {
         assert_eq!(invalid_info.name, None);
     }
 
+    fn setup_shared_palettes() -> (TempDir, PaletteLoader) {
+        let (temp_dir, _) = setup_test_palettes();
+        let shared_dir = temp_dir.path().join("shared");
+        fs::create_dir(&shared_dir).unwrap();
+        fs::write(shared_dir.join("shared.json"), r#"{"name": "Shared"}"#).unwrap();
+        fs::write(shared_dir.join("test.json"), r#"{"name": "Shadowed"}"#).unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path())
+            .with_palette_dirs(&[
+                shared_dir.display().to_string(),
+                temp_dir.path().join("missing").display().to_string(),
+            ])
+            .unwrap();
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_load_from_extra_palette_dir() {
+        let (temp_dir, loader) = setup_shared_palettes();
+
+        assert_eq!(loader.load("shared").unwrap().name, "Shared");
+        assert_eq!(
+            loader.palette_path("shared"),
+            temp_dir.path().join("shared").join("shared.json")
+        );
+    }
+
+    #[test]
+    fn test_default_palette_dir_takes_precedence() {
+        let (_temp_dir, loader) = setup_shared_palettes();
+
+        assert_eq!(loader.load("test").unwrap().name, "Test Palette");
+
+        let palettes = loader.list_all().unwrap();
+        let tests: Vec<_> = palettes.iter().filter(|p| p.filename == "test").collect();
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].name, Some("Test Palette".to_string()));
+        assert!(palettes.iter().any(|p| p.filename == "shared"));
+        assert_eq!(palettes.len(), 4);
+    }
+
+    #[test]
+    fn test_palette_dirs_expand_env_vars() {
+        let temp_dir = TempDir::new().unwrap();
+        let home = dirs::home_dir().unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path())
+            .with_palette_dirs(&["~/palettes".to_string()])
+            .unwrap();
+        assert_eq!(loader.extra_dirs, vec![home.join("palettes")]);
+
+        let result = PaletteLoader::new(temp_dir.path())
+            .with_palette_dirs(&["$THEMER_UNSET_DIR".to_string()]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_list_all_empty_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -236,6 +538,8 @@ mod tests {
         let nonexistent_path = temp_dir.path().join("nonexistent");
         let loader = PaletteLoader {
             palettes_dir: nonexistent_path,
+            extra_dirs: Vec::new(),
+            aliases: BTreeMap::new(),
         };
         let result = loader.list_all();
         assert!(result.is_err());
@@ -289,4 +593,362 @@ mod tests {
         let name = extract_palette_name(&test_file).unwrap();
         assert_eq!(name, "My Palette");
     }
+
+    const YAML_PALETTE: &str = r#"
+# Hand-edited twin of the JSON palette below
+name: Twin
+base_16:
+  base00: "282828"
+  base01: "3c3836"
+  base02: "504945"
+  base03: "665c54"
+  base04: "bdae93"
+  base05: "d5c4a1"
+  base06: "ebdbb2"
+  base07: "fbf1c7"
+  base08: "fb4934"
+  base09: "fe8019"
+  base0A: "fabd2f"
+  base0B: "b8bb26"
+  base0C: "8ec07c"
+  base0D: "83a598"
+  base0E: "d3869b"
+  base0F: "d65d0e"
+"#;
+
+    const JSON_PALETTE: &str = r#"{
+        "name": "Twin",
+        "base_16": {
+            "base00": "282828", "base01": "3c3836", "base02": "504945", "base03": "665c54",
+            "base04": "bdae93", "base05": "d5c4a1", "base06": "ebdbb2", "base07": "fbf1c7",
+            "base08": "fb4934", "base09": "fe8019", "base0A": "fabd2f", "base0B": "b8bb26",
+            "base0C": "8ec07c", "base0D": "83a598", "base0E": "d3869b", "base0F": "d65d0e"
+        }
+    }"#;
+
+    #[test]
+    fn test_load_yaml_matches_json_twin() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("twin.json"), JSON_PALETTE).unwrap();
+        fs::write(palettes_dir.join("twin.yaml"), YAML_PALETTE).unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let from_json = loader.load("twin.json").unwrap();
+        let from_yaml = loader.load("twin.yaml").unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&from_json).unwrap(),
+            serde_json::to_value(&from_yaml).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_load_without_extension_prefers_json_then_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("only_yml.yml"), YAML_PALETTE).unwrap();
+        fs::write(palettes_dir.join("both.json"), r#"{"name": "From JSON"}"#).unwrap();
+        fs::write(palettes_dir.join("both.yaml"), "name: From YAML").unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        assert_eq!(loader.load("only_yml").unwrap().name, "Twin");
+        assert_eq!(loader.load("both").unwrap().name, "From JSON");
+    }
+
+    #[test]
+    fn test_load_invalid_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("broken.yaml"), "name: [unclosed").unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let result = loader.load("broken");
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to parse palette YAML")
+        );
+    }
+
+    #[test]
+    fn test_list_all_includes_yaml_palettes() {
+        let (temp_dir, _) = setup_test_palettes();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::write(palettes_dir.join("twin.yaml"), YAML_PALETTE).unwrap();
+        fs::write(palettes_dir.join("short.yml"), "name: Short").unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let palettes = loader.list_all().unwrap();
+        assert_eq!(palettes.len(), 5);
+
+        let twin = palettes.iter().find(|p| p.filename == "twin").unwrap();
+        assert_eq!(twin.name, Some("Twin".to_string()));
+
+        let short = palettes.iter().find(|p| p.filename == "short").unwrap();
+        assert_eq!(short.name, Some("Short".to_string()));
+    }
+
+    #[test]
+    fn test_load_base16_scheme_from_palettes_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+
+        let scheme = YAML_PALETTE
+            .replace("name: Twin", "scheme: \"Twin Scheme\"\nauthor: \"someone\"")
+            .replace("base_16:\n", "")
+            .replace("  base", "base");
+        fs::write(palettes_dir.join("twin-scheme.yaml"), scheme).unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let palette = loader.load("twin-scheme").unwrap();
+        assert_eq!(palette.name, "Twin Scheme");
+        assert_eq!(palette.base16().unwrap().base0d, "83a598");
+        assert!(palette.base_30.is_none());
+
+        let palettes = loader.list_all().unwrap();
+        assert_eq!(palettes[0].name, Some("Twin Scheme".to_string()));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_hex() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(
+            palettes_dir.join("bad.json"),
+            JSON_PALETTE.replace("\"fb4934\"", "\"gggggg\""),
+        )
+        .unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let err = loader.load("bad").unwrap_err();
+        assert!(err.to_string().contains("Invalid palette"));
+        assert!(format!("{:#}", err).contains("base08 = 'gggggg'"));
+    }
+
+    #[test]
+    fn test_load_normalizes_mixed_hex_forms() {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(
+            palettes_dir.join("mixed.json"),
+            JSON_PALETTE
+                .replace("\"fb4934\"", "\"#FB4934\"")
+                .replace("\"282828\"", "\"#123\""),
+        )
+        .unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        let palette = loader.load("mixed").unwrap();
+        let base16 = palette.base16().unwrap();
+
+        assert_eq!(base16.base08, "fb4934");
+        assert_eq!(base16.base00, "112233");
+    }
+
+    #[test]
+    fn test_list_all_includes_nested_palettes() {
+        let (temp_dir, loader) = setup_test_palettes();
+        let dark_dir = temp_dir.path().join("palettes").join("dark");
+        fs::create_dir(&dark_dir).unwrap();
+        fs::write(dark_dir.join("nord.json"), JSON_PALETTE).unwrap();
+
+        let palettes = loader.list_all().unwrap();
+
+        let nested = palettes.iter().find(|p| p.filename == "dark/nord").unwrap();
+        assert_eq!(nested.name, Some("Twin".to_string()));
+        assert_eq!(palettes.len(), 4);
+    }
+
+    #[test]
+    fn test_load_nested_palette_by_relative_name() {
+        let (temp_dir, loader) = setup_test_palettes();
+        let light_dir = temp_dir.path().join("palettes").join("light").join("warm");
+        fs::create_dir_all(&light_dir).unwrap();
+        fs::write(light_dir.join("latte.json"), JSON_PALETTE).unwrap();
+
+        assert!(loader.exists("light/warm/latte"));
+        let palette = loader.load("light/warm/latte").unwrap();
+
+        assert_eq!(palette.name, "Twin");
+        assert!(
+            loader
+                .list_all()
+                .unwrap()
+                .iter()
+                .any(|p| p.filename == "light/warm/latte")
+        );
+    }
+
+    #[test]
+    fn test_load_resolves_alias() {
+        let (temp_dir, _) = setup_test_palettes();
+        fs::write(
+            temp_dir.path().join("palettes").join("nord.json"),
+            JSON_PALETTE,
+        )
+        .unwrap();
+        let aliases = BTreeMap::from([("mytheme".to_string(), "nord".to_string())]);
+
+        let loader = PaletteLoader::new(temp_dir.path()).with_aliases(&aliases);
+
+        assert_eq!(loader.resolve("mytheme"), "nord");
+        assert!(loader.exists("mytheme"));
+        assert_eq!(loader.load("mytheme").unwrap().name, "Twin");
+    }
+
+    #[test]
+    fn test_load_falls_back_to_literal_name_without_alias() {
+        let (temp_dir, _) = setup_test_palettes();
+        fs::write(
+            temp_dir.path().join("palettes").join("nord.json"),
+            JSON_PALETTE,
+        )
+        .unwrap();
+        let aliases = BTreeMap::from([("mytheme".to_string(), "nord".to_string())]);
+
+        let loader = PaletteLoader::new(temp_dir.path()).with_aliases(&aliases);
+
+        assert_eq!(loader.resolve("nord"), "nord");
+        assert_eq!(loader.load("nord").unwrap().name, "Twin");
+    }
+
+    #[test]
+    fn test_palette_from_path_outside_palettes_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("download.json");
+        fs::write(&path, JSON_PALETTE).unwrap();
+
+        let palette = Palette::from_path(&path).unwrap();
+
+        assert_eq!(palette.name, "Twin");
+        assert_eq!(palette.base16().unwrap().base08, "fb4934");
+    }
+
+    fn setup_inheritance(child: &str) -> (TempDir, PaletteLoader) {
+        let temp_dir = TempDir::new().unwrap();
+        let palettes_dir = temp_dir.path().join("palettes");
+        fs::create_dir(&palettes_dir).unwrap();
+        fs::write(palettes_dir.join("gruvbox.json"), JSON_PALETTE).unwrap();
+        fs::write(palettes_dir.join("child.json"), child).unwrap();
+
+        let loader = PaletteLoader::new(temp_dir.path());
+        (temp_dir, loader)
+    }
+
+    #[test]
+    fn test_load_inherits_unset_colors_from_base() {
+        let (_temp_dir, loader) = setup_inheritance(
+            r##"{"name": "Gruvbox Red", "base": "gruvbox", "base_16": {"base08": "#FF0000"}}"##,
+        );
+
+        let palette = loader.load("child").unwrap();
+        let base16 = palette.base16().unwrap();
+
+        assert_eq!(palette.name, "Gruvbox Red");
+        assert_eq!(palette.base.as_deref(), Some("gruvbox"));
+        assert_eq!(base16.base08, "ff0000");
+        assert_eq!(base16.base00, "282828");
+        assert_eq!(base16.base0f, "d65d0e");
+    }
+
+    #[test]
+    fn test_load_inherits_through_chain() {
+        let (temp_dir, loader) = setup_inheritance(
+            r#"{"name": "Child", "base": "middle", "base_16": {"base08": "ff0000"}}"#,
+        );
+        fs::write(
+            temp_dir.path().join("palettes").join("middle.json"),
+            r#"{"name": "Middle", "base": "gruvbox", "base_16": {"base08": "00ff00", "base09": "0000ff"}}"#,
+        )
+        .unwrap();
+
+        let base16 = loader.load("child").unwrap().base16().unwrap().clone();
+
+        assert_eq!(base16.base08, "ff0000");
+        assert_eq!(base16.base09, "0000ff");
+        assert_eq!(base16.base0a, "fabd2f");
+    }
+
+    #[test]
+    fn test_load_detects_inheritance_cycle() {
+        let (temp_dir, loader) = setup_inheritance(r#"{"name": "Child", "base": "loop"}"#);
+        fs::write(
+            temp_dir.path().join("palettes").join("loop.json"),
+            r#"{"name": "Loop", "base": "child"}"#,
+        )
+        .unwrap();
+
+        let err = loader.load("child").unwrap_err();
+
+        assert!(format!("{:#}", err).contains("Palette inheritance cycle: child -> loop -> child"));
+    }
+
+    #[test]
+    fn test_load_missing_base() {
+        let (_temp_dir, loader) = setup_inheritance(r#"{"name": "Child", "base": "deleted"}"#);
+
+        let err = loader.load("child").unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("Failed to load base palette 'deleted'")
+        );
+    }
+
+    #[test]
+    fn test_palette_from_path_rejects_base() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("child.json");
+        fs::write(&path, r#"{"name": "Child", "base": "gruvbox"}"#).unwrap();
+
+        let err = Palette::from_path(&path).unwrap_err();
+
+        assert!(err.to_string().contains("sets 'base'"));
+    }
+
+    #[test]
+    fn test_save_then_load_round_trips() {
+        let temp_dir = TempDir::new().unwrap();
+        let loader = PaletteLoader::new(temp_dir.path());
+        let mut palette: Palette = serde_json::from_str(JSON_PALETTE).unwrap();
+        palette.name = "dark/twin".to_string();
+        palette.base_30 = Some(palette.base16().unwrap().to_base30());
+        palette.colors = Some(BTreeMap::from([(
+            "primary".to_string(),
+            "fabd2f".to_string(),
+        )]));
+
+        let path = loader.save(&palette).unwrap();
+        let loaded = loader.load("dark/twin").unwrap();
+
+        assert_eq!(path, temp_dir.path().join("palettes/dark/twin.json"));
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&palette).unwrap()
+        );
+        let content = fs::read_to_string(path).unwrap();
+        assert!(content.contains("\"base0A\""));
+        assert!(!content.contains("\"base\""));
+    }
+
+    #[test]
+    fn test_save_omits_missing_base30() {
+        let temp_dir = TempDir::new().unwrap();
+        let loader = PaletteLoader::new(temp_dir.path());
+        let palette: Palette = serde_json::from_str(JSON_PALETTE).unwrap();
+
+        let path = loader.save(&palette).unwrap();
+        let loaded = loader.load("Twin").unwrap();
+
+        assert!(loaded.base_30.is_none());
+        assert!(!fs::read_to_string(path).unwrap().contains("base_30"));
+    }
 }
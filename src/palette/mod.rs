@@ -1,2 +1,4 @@
+pub mod color;
+pub mod extract;
 pub mod loader;
 pub mod models;
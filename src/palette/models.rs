@@ -1,13 +1,16 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum ColorError {
     #[error("Invalid hex color format: {0}")]
     InvalidFormat(String),
+    #[error("Expected 16 base16 colors, got {0}")]
+    Count(usize),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Base16 {
     pub base00: String,
     pub base01: String,
@@ -34,30 +37,168 @@ pub struct Base16 {
 }
 
 impl Base16 {
+    /// Builds a palette from colors in base00→base0F order, the same order
+    /// [`colors`](Self::colors) yields them in.
+    pub fn from_colors(colors: [String; 16]) -> Self {
+        let [
+            base00,
+            base01,
+            base02,
+            base03,
+            base04,
+            base05,
+            base06,
+            base07,
+            base08,
+            base09,
+            base0a,
+            base0b,
+            base0c,
+            base0d,
+            base0e,
+            base0f,
+        ] = colors;
+
+        Self {
+            base00,
+            base01,
+            base02,
+            base03,
+            base04,
+            base05,
+            base06,
+            base07,
+            base08,
+            base09,
+            base0a,
+            base0b,
+            base0c,
+            base0d,
+            base0e,
+            base0f,
+        }
+    }
+
+    /// Builds a palette with every slot set to `color`.
+    pub fn filled(color: &str) -> Self {
+        let mut base16 = Self::default();
+        for (_, slot) in base16.named_colors_mut() {
+            *slot = color.to_string();
+        }
+        base16
+    }
+
     pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.named_colors().map(|(_, color)| color)
+    }
+
+    /// Colors paired with their palette file keys, in base00→base0F order.
+    pub fn named_colors(&self) -> impl Iterator<Item = (&'static str, &str)> {
         [
-            self.base00.as_str(),
-            self.base01.as_str(),
-            self.base02.as_str(),
-            self.base03.as_str(),
-            self.base04.as_str(),
-            self.base05.as_str(),
-            self.base06.as_str(),
-            self.base07.as_str(),
-            self.base08.as_str(),
-            self.base09.as_str(),
-            self.base0a.as_str(),
-            self.base0b.as_str(),
-            self.base0c.as_str(),
-            self.base0d.as_str(),
-            self.base0e.as_str(),
-            self.base0f.as_str(),
+            ("base00", self.base00.as_str()),
+            ("base01", self.base01.as_str()),
+            ("base02", self.base02.as_str()),
+            ("base03", self.base03.as_str()),
+            ("base04", self.base04.as_str()),
+            ("base05", self.base05.as_str()),
+            ("base06", self.base06.as_str()),
+            ("base07", self.base07.as_str()),
+            ("base08", self.base08.as_str()),
+            ("base09", self.base09.as_str()),
+            ("base0A", self.base0a.as_str()),
+            ("base0B", self.base0b.as_str()),
+            ("base0C", self.base0c.as_str()),
+            ("base0D", self.base0d.as_str()),
+            ("base0E", self.base0e.as_str()),
+            ("base0F", self.base0f.as_str()),
         ]
         .into_iter()
     }
+
+    fn named_colors_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut String)> {
+        [
+            ("base00", &mut self.base00),
+            ("base01", &mut self.base01),
+            ("base02", &mut self.base02),
+            ("base03", &mut self.base03),
+            ("base04", &mut self.base04),
+            ("base05", &mut self.base05),
+            ("base06", &mut self.base06),
+            ("base07", &mut self.base07),
+            ("base08", &mut self.base08),
+            ("base09", &mut self.base09),
+            ("base0A", &mut self.base0a),
+            ("base0B", &mut self.base0b),
+            ("base0C", &mut self.base0c),
+            ("base0D", &mut self.base0d),
+            ("base0E", &mut self.base0e),
+            ("base0F", &mut self.base0f),
+        ]
+        .into_iter()
+    }
+
+    /// Derives a base30 palette from the 16 base colors.
+    ///
+    /// Follows the base16 styling guidelines: `base00`–`base07` run from the
+    /// darkest background to the lightest foreground, and `base08`–`base0F`
+    /// are the accents (red, orange, yellow, green, cyan, blue, purple, brown).
+    /// Base30 shades without a direct base16 counterpart reuse the closest one.
+    pub fn to_base30(&self) -> Base30 {
+        let c = |value: &String| value.clone();
+
+        Base30 {
+            // Backgrounds: base00 (default) through base03 (comments)
+            darker_black: c(&self.base00),
+            black: c(&self.base00),
+            black2: c(&self.base01),
+            one_bg: c(&self.base01),
+            one_bg2: c(&self.base02),
+            one_bg3: c(&self.base03),
+            line: c(&self.base02),
+            lightbg: c(&self.base02),
+            // Greys and foregrounds: base03 (comments) through base06
+            grey: c(&self.base03),
+            grey_fg: c(&self.base04),
+            grey_fg2: c(&self.base04),
+            light_grey: c(&self.base05),
+            white: c(&self.base05),
+            // Accents
+            red: c(&self.base08),
+            baby_pink: c(&self.base08),
+            orange: c(&self.base09),
+            yellow: c(&self.base0a),
+            sun: c(&self.base0a),
+            green: c(&self.base0b),
+            vibrant_green: c(&self.base0b),
+            teal: c(&self.base0c),
+            cyan: c(&self.base0c),
+            blue: c(&self.base0d),
+            nord_blue: c(&self.base0d),
+            purple: c(&self.base0e),
+            dark_purple: c(&self.base0e),
+            pink: c(&self.base0e),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Builds a palette from exactly 16 colors in base00→base0F order. Each
+/// color is normalized like palette files are, so `#FB4934` and `f00` work.
+impl TryFrom<Vec<String>> for Base16 {
+    type Error = ColorError;
+
+    fn try_from(colors: Vec<String>) -> Result<Self, Self::Error> {
+        let colors: Vec<String> = colors
+            .iter()
+            .map(|color| normalize_hex(color))
+            .collect::<Result<_, _>>()?;
+        let count = colors.len();
+        let colors: [String; 16] = colors.try_into().map_err(|_| ColorError::Count(count))?;
+
+        Ok(Self::from_colors(colors))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Base30 {
     pub white: String,
     pub darker_black: String,
@@ -88,26 +229,226 @@ pub struct Base30 {
     pub lightbg: String,
 }
 
+impl Base30 {
+    /// Builds a palette with every slot set to `color`.
+    pub fn filled(color: &str) -> Self {
+        let mut base30 = Self::default();
+        for (_, slot) in base30.named_colors_mut() {
+            *slot = color.to_string();
+        }
+        base30
+    }
+
+    /// Colors paired with their palette file keys, in declaration order.
+    pub fn named_colors(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("white", self.white.as_str()),
+            ("darker_black", self.darker_black.as_str()),
+            ("black", self.black.as_str()),
+            ("black2", self.black2.as_str()),
+            ("one_bg", self.one_bg.as_str()),
+            ("one_bg2", self.one_bg2.as_str()),
+            ("one_bg3", self.one_bg3.as_str()),
+            ("grey", self.grey.as_str()),
+            ("grey_fg", self.grey_fg.as_str()),
+            ("grey_fg2", self.grey_fg2.as_str()),
+            ("light_grey", self.light_grey.as_str()),
+            ("red", self.red.as_str()),
+            ("baby_pink", self.baby_pink.as_str()),
+            ("pink", self.pink.as_str()),
+            ("line", self.line.as_str()),
+            ("green", self.green.as_str()),
+            ("vibrant_green", self.vibrant_green.as_str()),
+            ("nord_blue", self.nord_blue.as_str()),
+            ("blue", self.blue.as_str()),
+            ("yellow", self.yellow.as_str()),
+            ("sun", self.sun.as_str()),
+            ("purple", self.purple.as_str()),
+            ("dark_purple", self.dark_purple.as_str()),
+            ("teal", self.teal.as_str()),
+            ("orange", self.orange.as_str()),
+            ("cyan", self.cyan.as_str()),
+            ("lightbg", self.lightbg.as_str()),
+        ]
+        .into_iter()
+    }
+
+    fn named_colors_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut String)> {
+        [
+            ("white", &mut self.white),
+            ("darker_black", &mut self.darker_black),
+            ("black", &mut self.black),
+            ("black2", &mut self.black2),
+            ("one_bg", &mut self.one_bg),
+            ("one_bg2", &mut self.one_bg2),
+            ("one_bg3", &mut self.one_bg3),
+            ("grey", &mut self.grey),
+            ("grey_fg", &mut self.grey_fg),
+            ("grey_fg2", &mut self.grey_fg2),
+            ("light_grey", &mut self.light_grey),
+            ("red", &mut self.red),
+            ("baby_pink", &mut self.baby_pink),
+            ("pink", &mut self.pink),
+            ("line", &mut self.line),
+            ("green", &mut self.green),
+            ("vibrant_green", &mut self.vibrant_green),
+            ("nord_blue", &mut self.nord_blue),
+            ("blue", &mut self.blue),
+            ("yellow", &mut self.yellow),
+            ("sun", &mut self.sun),
+            ("purple", &mut self.purple),
+            ("dark_purple", &mut self.dark_purple),
+            ("teal", &mut self.teal),
+            ("orange", &mut self.orange),
+            ("cyan", &mut self.cyan),
+            ("lightbg", &mut self.lightbg),
+        ]
+        .into_iter()
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum PaletteError {
     #[error("Palette is missing base_16 colors")]
     MissingBase16,
     #[error("Palette is missing base_30 colors")]
     MissingBase30,
+    #[error("Invalid base16 scheme: {0}")]
+    InvalidScheme(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    Color(#[from] ColorError),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Palette {
     pub name: String,
 
+    /// Parent palette this one inherits unset colors from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_30: Option<Base30>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_16: Option<Base16>,
+
+    /// Extra named colors, exposed to templates as `custom_<key>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colors: Option<BTreeMap<String, String>>,
+}
+
+/// Layout of a scheme file from the base16 ecosystem, e.g.
+/// `scheme: "Gruvbox"`, `author: "..."`, `base00: "282828"`, ...
+#[derive(Deserialize)]
+struct Base16Scheme {
+    scheme: String,
+    base00: String,
+    base01: String,
+    base02: String,
+    base03: String,
+    base04: String,
+    base05: String,
+    base06: String,
+    base07: String,
+    base08: String,
+    base09: String,
+    #[serde(rename = "base0A", alias = "base0a")]
+    base0a: String,
+    #[serde(rename = "base0B", alias = "base0b")]
+    base0b: String,
+    #[serde(rename = "base0C", alias = "base0c")]
+    base0c: String,
+    #[serde(rename = "base0D", alias = "base0d")]
+    base0d: String,
+    #[serde(rename = "base0E", alias = "base0e")]
+    base0e: String,
+    #[serde(rename = "base0F", alias = "base0f")]
+    base0f: String,
 }
 
 impl Palette {
+    /// Builds a palette from a base16 scheme YAML document.
+    ///
+    /// `scheme` becomes the palette name and the `base00`–`base0F` keys fill
+    /// `base_16`. Colors may be 3- or 6-digit hex, with or without `#`, and are
+    /// normalized to 6-digit lowercase.
+    pub fn from_base16_scheme(content: &str) -> Result<Self, PaletteError> {
+        let scheme: Base16Scheme = serde_yaml::from_str(content)?;
+
+        Ok(Self {
+            name: scheme.scheme,
+            base: None,
+            colors: None,
+            base_30: None,
+            base_16: Some(Base16 {
+                base00: normalize_hex(&scheme.base00)?,
+                base01: normalize_hex(&scheme.base01)?,
+                base02: normalize_hex(&scheme.base02)?,
+                base03: normalize_hex(&scheme.base03)?,
+                base04: normalize_hex(&scheme.base04)?,
+                base05: normalize_hex(&scheme.base05)?,
+                base06: normalize_hex(&scheme.base06)?,
+                base07: normalize_hex(&scheme.base07)?,
+                base08: normalize_hex(&scheme.base08)?,
+                base09: normalize_hex(&scheme.base09)?,
+                base0a: normalize_hex(&scheme.base0a)?,
+                base0b: normalize_hex(&scheme.base0b)?,
+                base0c: normalize_hex(&scheme.base0c)?,
+                base0d: normalize_hex(&scheme.base0d)?,
+                base0e: normalize_hex(&scheme.base0e)?,
+                base0f: normalize_hex(&scheme.base0f)?,
+            }),
+        })
+    }
+
+    /// Rewrites every base16, base30 and custom color to bare 6-digit
+    /// lowercase hex, accepting the forms [`normalize_hex`] does. Fails on the
+    /// first color that can't be normalized.
+    pub fn normalize(&mut self) -> Result<(), ColorError> {
+        let base16 = self.base_16.iter_mut().flat_map(|b| b.named_colors_mut());
+        let base30 = self.base_30.iter_mut().flat_map(|b| b.named_colors_mut());
+
+        for (field, color) in base16.chain(base30) {
+            *color = normalize_hex(color)
+                .map_err(|_| ColorError::InvalidFormat(format!("{} = '{}'", field, color)))?;
+        }
+
+        for (key, color) in self.colors.iter_mut().flatten() {
+            *color = normalize_hex(color)
+                .map_err(|_| ColorError::InvalidFormat(format!("colors.{} = '{}'", key, color)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that every base16, base30 and custom color is a bare 6-digit
+    /// hex value.
+    pub fn validate(&self) -> Result<(), ColorError> {
+        let base16 = self.base_16.iter().flat_map(|b| b.named_colors());
+        let base30 = self.base_30.iter().flat_map(|b| b.named_colors());
+
+        for (field, color) in base16.chain(base30) {
+            if !is_valid_hex(color) {
+                return Err(ColorError::InvalidFormat(format!(
+                    "{} = '{}'",
+                    field, color
+                )));
+            }
+        }
+
+        for (key, color) in self.colors.iter().flatten() {
+            if !is_valid_hex(color) {
+                return Err(ColorError::InvalidFormat(format!(
+                    "colors.{} = '{}'",
+                    key, color
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn base16(&self) -> Result<&Base16, PaletteError> {
         self.base_16.as_ref().ok_or(PaletteError::MissingBase16)
     }
@@ -116,3 +457,216 @@ impl Palette {
         self.base_30.as_ref().ok_or(PaletteError::MissingBase30)
     }
 }
+
+/// Whether `value` is a bare 6-digit hex color (no `#`, any case).
+pub fn is_valid_hex(value: &str) -> bool {
+    value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Normalizes a hex color to bare 6-digit lowercase form.
+///
+/// Accepts an optional leading `#` and expands 3-digit shorthand (`f0a` -> `ff00aa`).
+pub fn normalize_hex(value: &str) -> Result<String, ColorError> {
+    let hex = value.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ColorError::InvalidFormat(value.to_string()));
+    }
+
+    match hex.len() {
+        6 => Ok(hex.to_ascii_lowercase()),
+        3 => Ok(hex
+            .chars()
+            .flat_map(|c| [c, c])
+            .collect::<String>()
+            .to_ascii_lowercase()),
+        _ => Err(ColorError::InvalidFormat(value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRUVBOX_SCHEME: &str = r##"
+scheme: "Gruvbox dark, hard"
+author: "Dawid Kurek (dawikur@gmail.com), morhetz (https://github.com/morhetz/gruvbox)"
+base00: "1d2021" # ----
+base01: "3c3836" # ---
+base02: "504945" # --
+base03: "665c54" # -
+base04: "bdae93" # +
+base05: "d5c4a1" # ++
+base06: "ebdbb2" # +++
+base07: "fbf1c7" # ++++
+base08: "FB4934" # red
+base09: "#fe8019" # orange
+base0A: "fabd2f" # yellow
+base0B: "b8bb26" # green
+base0C: "8ec07c" # aqua/cyan
+base0D: "83a598" # blue
+base0E: "d3869b" # purple
+base0F: "#d65" # brown
+"##;
+
+    #[test]
+    fn test_from_base16_scheme() {
+        let palette = Palette::from_base16_scheme(GRUVBOX_SCHEME).unwrap();
+
+        assert_eq!(palette.name, "Gruvbox dark, hard");
+        assert!(palette.base_30.is_none());
+
+        let base16 = palette.base16().unwrap();
+        assert_eq!(base16.base00, "1d2021");
+        assert_eq!(base16.base08, "fb4934");
+        assert_eq!(base16.base09, "fe8019");
+        assert_eq!(base16.base0a, "fabd2f");
+        assert_eq!(base16.base0f, "dd6655");
+    }
+
+    #[test]
+    fn test_from_base16_scheme_missing_key() {
+        let content = "scheme: \"Broken\"\nbase00: \"000000\"\n";
+        let result = Palette::from_base16_scheme(content);
+        assert!(matches!(result, Err(PaletteError::InvalidScheme(_))));
+    }
+
+    #[test]
+    fn test_from_base16_scheme_invalid_color() {
+        let content = GRUVBOX_SCHEME.replace("\"fabd2f\"", "\"fabd2\"");
+        let result = Palette::from_base16_scheme(&content);
+        assert!(matches!(result, Err(PaletteError::Color(_))));
+    }
+
+    #[test]
+    fn test_to_base30_maps_accents() {
+        let palette = Palette::from_base16_scheme(GRUVBOX_SCHEME).unwrap();
+        let base16 = palette.base16().unwrap();
+
+        let base30 = base16.to_base30();
+
+        assert_eq!(base30.black, base16.base00);
+        assert_eq!(base30.white, base16.base05);
+        assert_eq!(base30.red, base16.base08);
+        assert_eq!(base30.orange, base16.base09);
+        assert_eq!(base30.green, base16.base0b);
+        assert_eq!(base30.blue, base16.base0d);
+        assert_eq!(base30.purple, base16.base0e);
+    }
+
+    fn create_valid_palette() -> Palette {
+        let mut palette = Palette::from_base16_scheme(GRUVBOX_SCHEME).unwrap();
+        palette.base_30 = Some(palette.base16().unwrap().to_base30());
+        palette
+    }
+
+    #[test]
+    fn test_validate_all_valid() {
+        let palette = create_valid_palette();
+        assert!(palette.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_short_hex() {
+        let mut palette = create_valid_palette();
+        palette.base_16.as_mut().unwrap().base0c = "f00".to_string();
+
+        let err = palette.validate().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid hex color format: base0C = 'f00'");
+    }
+
+    #[test]
+    fn test_validate_non_hex_value() {
+        let mut palette = create_valid_palette();
+        palette.base_30.as_mut().unwrap().teal = "gggggg".to_string();
+
+        let err = palette.validate().unwrap_err();
+        assert!(err.to_string().contains("teal = 'gggggg'"));
+    }
+
+    #[test]
+    fn test_validate_palette_without_colors() {
+        let palette = Palette {
+            name: "empty".to_string(),
+            base: None,
+            base_16: None,
+            base_30: None,
+            colors: None,
+        };
+        assert!(palette.validate().is_ok());
+    }
+
+    #[test]
+    fn test_normalize_hex() {
+        assert_eq!(normalize_hex("ff0000").unwrap(), "ff0000");
+        assert_eq!(normalize_hex("#FF0000").unwrap(), "ff0000");
+        assert_eq!(normalize_hex("#f00").unwrap(), "ff0000");
+        assert_eq!(normalize_hex("F00").unwrap(), "ff0000");
+        assert!(normalize_hex("ff00").is_err());
+        assert!(normalize_hex("gggggg").is_err());
+        assert!(normalize_hex("").is_err());
+    }
+
+    #[test]
+    fn test_palette_normalize_variants() {
+        for variant in ["#FF0000", "ff0000", "#f00", "F00", " #Ff0000 "] {
+            let mut palette = create_valid_palette();
+            palette.base_16.as_mut().unwrap().base08 = variant.to_string();
+            palette.base_30.as_mut().unwrap().red = variant.to_string();
+
+            palette.normalize().unwrap();
+
+            assert_eq!(palette.base16().unwrap().base08, "ff0000", "{}", variant);
+            assert_eq!(palette.base30().unwrap().red, "ff0000", "{}", variant);
+            assert!(palette.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_palette_normalize_reports_field() {
+        let mut palette = create_valid_palette();
+        palette.base_30.as_mut().unwrap().teal = "#ff00".to_string();
+
+        let err = palette.normalize().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid hex color format: teal = '#ff00'");
+    }
+
+    #[test]
+    fn test_base16_from_colors_round_trips() {
+        let base16 = create_valid_palette().base16().unwrap().clone();
+        let colors: Vec<String> = base16.colors().map(String::from).collect();
+
+        let rebuilt = Base16::from_colors(colors.clone().try_into().unwrap());
+
+        assert!(rebuilt.colors().eq(base16.colors()));
+        assert_eq!(rebuilt.base0a, base16.base0a);
+    }
+
+    #[test]
+    fn test_base16_try_from_vec() {
+        let colors: Vec<String> = (0..16).map(|i| format!("#{:X}{:X}{:X}", i, i, i)).collect();
+
+        let base16 = Base16::try_from(colors).unwrap();
+
+        assert_eq!(base16.base00, "000000");
+        assert_eq!(base16.base0f, "ffffff");
+    }
+
+    #[test]
+    fn test_base16_try_from_wrong_length() {
+        let colors = vec!["000000".to_string(); 15];
+
+        let err = Base16::try_from(colors).unwrap_err();
+        assert_eq!(err.to_string(), "Expected 16 base16 colors, got 15");
+    }
+
+    #[test]
+    fn test_base16_try_from_invalid_hex() {
+        let mut colors = vec!["000000".to_string(); 16];
+        colors[3] = "nothex".to_string();
+
+        let err = Base16::try_from(colors).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid hex color format: nothex");
+    }
+}
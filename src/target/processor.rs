@@ -0,0 +1,1545 @@
+use anyhow::{Context as AnyhowContext, Result};
+use std::borrow::Cow;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use tera::Context;
+
+use crate::config::models::{Mode, Target};
+use crate::output::output;
+use crate::palette::models::Palette;
+use crate::template::engine::TemplateEngine;
+
+const POST_APPLY_TIMEOUT: Duration = Duration::from_secs(10);
+const HEADER_TEXT: &str = "DO NOT EDIT — generated by themer";
+/// File name of the wallpaper copy in the cache directory.
+const CACHED_WALLPAPER: &str = "wallpaper";
+
+pub struct TargetProcessor {
+    templates_dir: PathBuf,
+    engine: TemplateEngine,
+    output_dir: Option<PathBuf>,
+    cache_dir: Option<String>,
+    reload: bool,
+    force: bool,
+    partials_loaded: bool,
+}
+
+impl TargetProcessor {
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            templates_dir: config_dir.join("templates"),
+            engine: TemplateEngine::new(),
+            output_dir: None,
+            cache_dir: None,
+            reload: true,
+            force: false,
+            partials_loaded: false,
+        }
+    }
+
+    /// Overrides where include-mode outputs are written. An empty string
+    /// keeps the system cache directory. `~` and `$VAR` are expanded when the
+    /// directory is first needed.
+    pub fn with_cache_dir(mut self, cache_dir: &str) -> Self {
+        if !cache_dir.is_empty() {
+            self.cache_dir = Some(cache_dir.to_string());
+        }
+        self
+    }
+
+    /// Redirects every target into `output_dir`, named like include-mode
+    /// outputs, and disables backups and reload commands.
+    pub fn with_output_dir(mut self, output_dir: PathBuf) -> Self {
+        self.output_dir = Some(output_dir);
+        self
+    }
+
+    /// Skips targets' reload commands while still writing their outputs.
+    pub fn without_reload(mut self) -> Self {
+        self.reload = false;
+        self
+    }
+
+    /// Writes and reloads targets even when their output is unchanged.
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Renders the target, writes it and runs its reload command. Targets
+    /// whose output already matches are left alone, reload included, unless
+    /// the processor was built [`with_force`](Self::with_force). A target
+    /// with several templates writes each file (see [`template_parts`]) and
+    /// reloads once afterwards.
+    pub fn process(&mut self, target: &Target, context: &Context, palette: &Palette) -> Result<()> {
+        let parts = template_parts(target);
+        if parts.is_empty() {
+            anyhow::bail!("Target '{}' has no template", target.name);
+        }
+
+        let mut changed = false;
+        let mut output_paths = Vec::new();
+        for part in &parts {
+            let (written, output_path) = self.write_output(part, context)?;
+            changed |= written;
+            output_paths.push(output_path);
+        }
+
+        if !changed {
+            output::item(Some("="), &target.name, Some("unchanged"));
+            return Ok(());
+        }
+
+        if self.reload && !target.reload_cmd.is_empty() && self.output_dir.is_none() {
+            // With several files, `{output}` is the directory holding them.
+            let output_path = match output_paths.as_slice() {
+                [single] => single.as_path(),
+                [first, ..] => first.parent().unwrap_or(first),
+                [] => unreachable!("targets without templates bail above"),
+            };
+
+            self.handle_reload_command(
+                &target.reload_cmd,
+                &target.name,
+                &palette.name,
+                output_path,
+                Duration::from_secs(target.reload_timeout),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders and writes a single-template target, returning whether
+    /// anything was written and where the output went.
+    fn write_output(&mut self, target: &Target, context: &Context) -> Result<(bool, PathBuf)> {
+        let (rendered, output_path) = self.render_output(target, context)?;
+
+        if !self.force && self.is_unchanged(target, &output_path, &rendered) {
+            if let Some(mode_bits) = &target.mode_bits {
+                set_mode_bits(&output_path, mode_bits).with_context(|| {
+                    format!("Failed to set permissions on: {}", output_path.display())
+                })?;
+            }
+            return Ok((false, output_path));
+        }
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        if target.mode == Mode::Replace && target.backup && self.output_dir.is_none() {
+            self.backup_existing(&output_path)?;
+        }
+
+        write_atomic(&output_path, &rendered)
+            .with_context(|| format!("Failed to write file: {}", output_path.display()))?;
+
+        if let Some(mode_bits) = &target.mode_bits {
+            set_mode_bits(&output_path, mode_bits).with_context(|| {
+                format!("Failed to set permissions on: {}", output_path.display())
+            })?;
+        }
+
+        if target.mode == Mode::Symlink && self.output_dir.is_none() {
+            let link_path = expand_path(&target.output)?;
+            if target.backup && !link_path.is_symlink() {
+                self.backup_existing(&link_path)?;
+            }
+
+            replace_symlink(&link_path, &output_path)
+                .with_context(|| format!("Failed to create symlink: {}", link_path.display()))?;
+        }
+
+        Ok((true, output_path))
+    }
+
+    /// Whether `output_path` already holds `rendered` and, for symlink
+    /// targets, the link already points at it.
+    fn is_unchanged(&self, target: &Target, output_path: &Path, rendered: &str) -> bool {
+        if fs::read_to_string(output_path).ok().as_deref() != Some(rendered) {
+            return false;
+        }
+
+        if target.mode == Mode::Symlink && self.output_dir.is_none() {
+            let link = expand_path(&target.output).and_then(|link| Ok(fs::read_link(link)?));
+            return link.is_ok_and(|destination| destination == output_path);
+        }
+
+        true
+    }
+
+    /// Renders the target and reports where it would be written, without
+    /// touching the filesystem or running the reload command.
+    pub fn process_dry_run(&mut self, target: &Target, context: &Context) -> Result<()> {
+        for part in template_parts(target) {
+            self.render(&part, context)?;
+            let output_path = self.resolve_output_path(&part)?;
+
+            output::item(
+                Some("dry-run"),
+                &target.name,
+                Some(&output_path.display().to_string()),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Renders the target exactly as [`process`](Self::process) would write
+    /// it, generated-file header included, along with where it goes.
+    pub fn render_output(
+        &mut self,
+        target: &Target,
+        context: &Context,
+    ) -> Result<(String, PathBuf)> {
+        let rendered = self.render(target, context)?;
+        let output_path = self.resolve_output_path(target)?;
+
+        if target.header {
+            return Ok((with_header(&rendered, &output_path), output_path));
+        }
+        Ok((rendered, output_path))
+    }
+
+    /// Renders the target's template. Each template file is read and parsed
+    /// once per processor, so targets sharing a template reuse it.
+    pub fn render(&mut self, target: &Target, context: &Context) -> Result<String> {
+        if !self.partials_loaded {
+            self.engine.load_partials(&self.templates_dir)?;
+            self.partials_loaded = true;
+        }
+
+        if !self.engine.has_template(&target.template) {
+            let template_path = self.templates_dir.join(&target.template);
+            let template_content = fs::read_to_string(&template_path)
+                .with_context(|| format!("Failed to read template: {}", template_path.display()))?;
+
+            self.engine
+                .add_template(&target.template, &template_content)
+                .with_context(|| format!("Failed to render template for {}", target.name))?;
+        }
+
+        self.engine
+            .render_template(&target.template, &with_target_vars(target, context))
+            .with_context(|| format!("Failed to render template for {}", target.name))
+    }
+
+    /// Copies an existing output file to `<path>.themer.bak` before it gets
+    /// overwritten.
+    fn backup_existing(&self, output_path: &Path) -> Result<()> {
+        if !output_path.is_file() {
+            return Ok(());
+        }
+
+        let mut backup_path = output_path.as_os_str().to_owned();
+        backup_path.push(".themer.bak");
+        let backup_path = PathBuf::from(backup_path);
+
+        fs::copy(output_path, &backup_path)
+            .with_context(|| format!("Failed to back up file: {}", output_path.display()))?;
+
+        output::item(
+            Some("backup"),
+            &output_path.display().to_string(),
+            Some(&backup_path.display().to_string()),
+        );
+
+        Ok(())
+    }
+
+    pub fn resolve_output_path(&self, target: &Target) -> Result<PathBuf> {
+        if let Some(output_dir) = &self.output_dir {
+            return Ok(output_dir.join(generated_filename(target)));
+        }
+
+        match target.mode {
+            Mode::Include => Ok(self.cache_dir()?.join(generated_filename(target))),
+            Mode::Symlink => {
+                if target.output.is_empty() {
+                    anyhow::bail!(
+                        "Target '{}' with mode 'Symlink' requires an 'output' field",
+                        target.name
+                    );
+                }
+
+                // The rendered file lives in the cache; `output` becomes a
+                // symlink to it.
+                Ok(self.cache_dir()?.join(generated_filename(target)))
+            }
+            Mode::Replace => {
+                if target.output.is_empty() {
+                    anyhow::bail!(
+                        "Target '{}' with mode 'Replace' requires an 'output' field",
+                        target.name
+                    );
+                }
+
+                expand_path(&target.output)
+            }
+        }
+    }
+
+    fn cache_dir(&self) -> Result<PathBuf> {
+        resolve_cache_dir(self.cache_dir.as_deref().unwrap_or_default())
+    }
+
+    fn handle_reload_command(
+        &self,
+        reload_cmd: &str,
+        target_name: &str,
+        theme_name: &str,
+        output_path: &Path,
+        timeout: Duration,
+    ) -> Result<()> {
+        let command = expand_reload_placeholders(reload_cmd, target_name, theme_name, output_path);
+        self.run_command(&command, target_name, timeout)
+    }
+
+    /// Runs the global `post_apply_cmd` once after every target has been
+    /// processed. `{palette}` and `{theme}` are replaced with the palette name.
+    pub fn run_post_apply_command(&self, post_apply_cmd: &str, palette_name: &str) -> Result<()> {
+        if post_apply_cmd.is_empty() || self.output_dir.is_some() {
+            return Ok(());
+        }
+
+        let command = post_apply_cmd
+            .replace("{theme}", palette_name)
+            .replace("{palette}", palette_name);
+
+        self.run_command(&command, "post-apply hook", POST_APPLY_TIMEOUT)
+    }
+
+    fn run_command(&self, command: &str, label: &str, timeout: Duration) -> Result<()> {
+        if command.trim().ends_with('&') {
+            output::info(&format!("Spawning background command for {}", label));
+            self.execute_background_command(command)?;
+            output::success("Background command spawned");
+        } else {
+            output::info(&format!("Executing reload command for {}", label));
+            if self.execute_foreground_command(command, timeout)? {
+                output::success("Application reloaded");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs `command` and waits for it to finish, killing it once `timeout`
+    /// elapses. Returns whether the command completed successfully.
+    fn execute_foreground_command(&self, command: &str, timeout: Duration) -> Result<bool> {
+        if command.is_empty() {
+            return Ok(true);
+        }
+
+        let mut shell = Command::new("sh");
+        shell
+            .args(["-c", command])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        // Run the command in its own process group so a timeout can take down
+        // everything it started, not just the `sh` wrapper.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut shell, 0);
+
+        let mut child = match shell.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                output::error(&format!("Could not execute command: {}", e));
+                return Ok(false);
+            }
+        };
+
+        // Drain stderr on a separate thread so a chatty command cannot block
+        // on a full pipe while we wait for it.
+        let mut stderr = child.stderr.take();
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buffer = String::new();
+            if let Some(stderr) = stderr.as_mut() {
+                let _ = stderr.read_to_string(&mut buffer);
+            }
+            buffer
+        });
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            if started.elapsed() >= timeout {
+                kill_process_group(&child);
+                let _ = child.kill();
+                let _ = child.wait();
+                output::warning(&format!(
+                    "Command timed out after {}s and was killed: {}",
+                    timeout.as_secs(),
+                    command
+                ));
+                return Ok(false);
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        };
+
+        if status.success() {
+            return Ok(true);
+        }
+
+        let stderr = stderr_reader.join().unwrap_or_default();
+        output::warning(&format!("Warning executing command: {}", stderr));
+        Ok(false)
+    }
+
+    fn execute_background_command(&self, command: &str) -> Result<()> {
+        if command.is_empty() {
+            return Ok(());
+        }
+
+        Command::new("sh")
+            .args(["-c", command])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to spawn background command: {}", command))?;
+
+        std::thread::sleep(Duration::from_millis(100));
+
+        Ok(())
+    }
+
+    pub fn cache_wallpaper(&self, wallpaper_path: &Path) -> Result<()> {
+        let cache_dir = self.cache_dir()?;
+
+        fs::create_dir_all(&cache_dir).with_context(|| {
+            format!("Failed to create cache directory: {}", cache_dir.display())
+        })?;
+
+        let wallpaper_dest = cache_dir.join(CACHED_WALLPAPER);
+
+        fs::copy(wallpaper_path, &wallpaper_dest).with_context(|| {
+            format!(
+                "Failed to copy wallpaper to cache: {}",
+                wallpaper_dest.display()
+            )
+        })?;
+
+        output::item(
+            Some("→"),
+            "Cached wallpaper",
+            Some(&wallpaper_dest.display().to_string()),
+        );
+
+        Ok(())
+    }
+
+    /// Every path themer writes into the cache directory for `targets`:
+    /// include and symlink outputs, plus the cached wallpaper. Targets whose
+    /// path can't be resolved never wrote anything there and are skipped.
+    pub fn cached_files(&self, targets: &[Target]) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.cache_dir()?.join(CACHED_WALLPAPER)];
+
+        for target in targets {
+            if target.mode == Mode::Replace {
+                continue;
+            }
+            for part in template_parts(target) {
+                if let Ok(path) = self.resolve_output_path(&part) {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+}
+
+/// Expands `~` and `$VAR`/`${VAR}` references in a configured path. Undefined
+/// variables are an error rather than being left in the path literally.
+pub fn expand_path(path: &str) -> Result<PathBuf> {
+    let expanded =
+        shellexpand::full(path).with_context(|| format!("Failed to expand '{}'", path))?;
+    Ok(PathBuf::from(expanded.into_owned()))
+}
+
+/// Where include-mode outputs and the cached wallpaper live: the configured
+/// `cache_dir` (expanded), or `<system cache>/themer` when it's empty.
+pub fn resolve_cache_dir(cache_dir: &str) -> Result<PathBuf> {
+    if cache_dir.is_empty() {
+        return Ok(dirs::cache_dir()
+            .context("Could not find cache directory")?
+            .join("themer"));
+    }
+
+    expand_path(cache_dir)
+}
+
+/// Extensions that only mark a file as a template. They are dropped before
+/// naming the output, so `kitty.conf.tmpl` renders to `<name>.conf`.
+const TEMPLATE_EXTENSIONS: [&str; 3] = ["tmpl", "tera", "j2"];
+
+/// Drops a trailing template marker (`.tmpl`, `.tera`, `.j2`) from a
+/// template's path.
+pub(crate) fn strip_template_extension(template: &Path) -> &Path {
+    match template.file_stem() {
+        Some(stem)
+            if template
+                .extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| TEMPLATE_EXTENSIONS.contains(&ext)) =>
+        {
+            Path::new(stem)
+        }
+        _ => template,
+    }
+}
+
+/// Splits a target into one single-template target per template file.
+///
+/// A target with a single template is returned as is. With several, each
+/// part is named `<name>-<template stem>`, so include-mode files don't
+/// collide in the cache, and `output` is treated as a directory: each file
+/// goes to `<output>/<template file name>`, minus a template marker.
+pub fn template_parts(target: &Target) -> Vec<Cow<'_, Target>> {
+    let files: Vec<&str> = target.template_files().collect();
+
+    match files.as_slice() {
+        [file] if *file == target.template && target.templates.is_empty() => {
+            vec![Cow::Borrowed(target)]
+        }
+        [file] => vec![Cow::Owned(Target {
+            template: file.to_string(),
+            templates: Vec::new(),
+            ..target.clone()
+        })],
+        _ => files
+            .iter()
+            .map(|file| {
+                let file_name = Path::new(file)
+                    .file_name()
+                    .map_or(Path::new(file), Path::new);
+                let file_name = strip_template_extension(file_name);
+                let stem = file_name
+                    .file_stem()
+                    .map_or_else(|| file.to_string(), |s| s.to_string_lossy().into_owned());
+
+                let output = if target.output.is_empty() {
+                    String::new()
+                } else {
+                    Path::new(&target.output)
+                        .join(file_name)
+                        .display()
+                        .to_string()
+                };
+
+                Cow::Owned(Target {
+                    name: format!("{}-{}", target.name, stem),
+                    template: file.to_string(),
+                    templates: Vec::new(),
+                    output,
+                    ..target.clone()
+                })
+            })
+            .collect(),
+    }
+}
+
+/// File name for outputs themer places itself: the target name plus the
+/// template's extension, ignoring a trailing template marker.
+fn generated_filename(target: &Target) -> String {
+    let extension = strip_template_extension(Path::new(&target.template))
+        .extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("");
+
+    if extension.is_empty() {
+        target.name.clone()
+    } else {
+        format!("{}.{}", target.name, extension)
+    }
+}
+
+/// Adds the target's `vars` on top of the palette context. The shared
+/// context is only cloned when there is something to add, so one target's
+/// vars never reach another.
+pub fn with_target_vars<'a>(target: &Target, context: &'a Context) -> Cow<'a, Context> {
+    if target.vars.is_empty() {
+        return Cow::Borrowed(context);
+    }
+
+    let mut context = context.clone();
+    for (key, value) in &target.vars {
+        context.insert(key, value);
+    }
+    Cow::Owned(context)
+}
+
+/// Comment delimiters for the output's file type, or `None` when the
+/// extension isn't one we know how to comment. CSS only has block comments.
+fn comment_style(path: &Path) -> Option<(&'static str, &'static str)> {
+    let extension = path.extension()?.to_str()?;
+
+    match extension {
+        "conf" | "toml" | "sh" | "bash" | "zsh" | "fish" | "yaml" | "yml" | "py" => Some(("#", "")),
+        "js" | "ts" | "jsonc" | "rasi" | "scss" => Some(("//", "")),
+        "css" => Some(("/*", " */")),
+        "lua" => Some(("--", "")),
+        _ => None,
+    }
+}
+
+/// Prepends the do-not-edit comment to `rendered`, keeping a leading shebang
+/// on the first line.
+fn with_header(rendered: &str, output_path: &Path) -> String {
+    let Some((open, close)) = comment_style(output_path) else {
+        return rendered.to_string();
+    };
+    let header = format!("{} {}{}\n", open, HEADER_TEXT, close);
+
+    if rendered.starts_with("#!") {
+        let (shebang, rest) = rendered.split_once('\n').unwrap_or((rendered, ""));
+        format!("{}\n{}{}", shebang, header, rest)
+    } else {
+        format!("{}{}", header, rendered)
+    }
+}
+
+/// Placeholders [`expand_reload_placeholders`] substitutes in a reload
+/// command, without their braces.
+const RELOAD_PLACEHOLDERS: [&str; 4] = ["theme", "palette", "target", "output"];
+
+/// Describes `{word}` tokens in the target's `reload_cmd` or `output` that
+/// would be left in place, such as a typo like `{them}`. Outputs take no
+/// placeholders at all; `${VAR}` is expanded separately and not reported.
+pub fn placeholder_problems(target: &Target) -> Vec<String> {
+    let reload = unknown_placeholders(&target.reload_cmd, &RELOAD_PLACEHOLDERS)
+        .into_iter()
+        .map(|token| {
+            format!(
+                "Target '{}' reload_cmd has unknown placeholder '{}'",
+                target.name, token
+            )
+        });
+    let output = unknown_placeholders(&target.output, &[])
+        .into_iter()
+        .map(|token| {
+            format!(
+                "Target '{}' output has unresolved placeholder '{}'",
+                target.name, token
+            )
+        });
+
+    reload.chain(output).collect()
+}
+
+/// Returns each `{word}` token in `text` whose word isn't in `known`. Braces
+/// around anything other than a plain word (shell blocks, `${VAR}`) are
+/// skipped.
+fn unknown_placeholders<'a>(text: &'a str, known: &[&str]) -> Vec<&'a str> {
+    let mut tokens = Vec::new();
+
+    for (start, _) in text.match_indices('{') {
+        if text[..start].ends_with('$') {
+            continue;
+        }
+        let Some(len) = text[start..].find('}') else {
+            continue;
+        };
+
+        let token = &text[start..=start + len];
+        let word = &token[1..token.len() - 1];
+        if !word.is_empty()
+            && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !known.contains(&word)
+        {
+            tokens.push(token);
+        }
+    }
+
+    tokens
+}
+
+/// Substitutes the `{theme}`, `{palette}`, `{target}` and `{output}`
+/// placeholders in a reload command.
+fn expand_reload_placeholders(
+    reload_cmd: &str,
+    target_name: &str,
+    palette_name: &str,
+    output_path: &Path,
+) -> String {
+    reload_cmd
+        .replace("{theme}", palette_name)
+        .replace("{palette}", palette_name)
+        .replace("{target}", target_name)
+        .replace("{output}", &output_path.display().to_string())
+}
+
+/// Kills the process group led by `child`, which `execute_foreground_command`
+/// spawns with its own group id.
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_child: &Child) {}
+
+/// Points `link` at `destination`, atomically replacing whatever file or
+/// symlink is already there.
+#[cfg(unix)]
+fn replace_symlink(link: &Path, destination: &Path) -> Result<()> {
+    if let Some(parent) = link.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file_name = link
+        .file_name()
+        .context("Output path has no file name")?
+        .to_string_lossy();
+    let temp_link = link.with_file_name(format!(".{}.themer.tmp", file_name));
+
+    let _ = fs::remove_file(&temp_link);
+    std::os::unix::fs::symlink(destination, &temp_link)?;
+
+    if let Err(e) = fs::rename(&temp_link, link) {
+        let _ = fs::remove_file(&temp_link);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn replace_symlink(_link: &Path, _destination: &Path) -> Result<()> {
+    anyhow::bail!("Mode 'symlink' is only supported on Unix")
+}
+
+/// Parses a target's `mode_bits`, an octal permission string such as
+/// `"0755"` (an `0o` prefix is accepted too).
+pub fn parse_mode_bits(mode_bits: &str) -> Result<u32> {
+    let digits = mode_bits.strip_prefix("0o").unwrap_or(mode_bits);
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if !digits.is_empty() && mode <= 0o7777 => Ok(mode),
+        _ => anyhow::bail!(
+            "Invalid mode_bits '{}', expected octal permissions like \"0755\"",
+            mode_bits
+        ),
+    }
+}
+
+#[cfg(unix)]
+fn set_mode_bits(path: &Path, mode_bits: &str) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = parse_mode_bits(mode_bits)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode_bits(path: &Path, _mode_bits: &str) -> Result<()> {
+    output::warning(&format!(
+        "mode_bits is only supported on Unix, leaving {} as is",
+        path.display()
+    ));
+    Ok(())
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it into
+/// place, so readers never observe a partially written file.
+///
+/// Symlinked destinations are resolved first so the link itself survives, and
+/// the permissions of an existing file are carried over.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let path = if path.is_symlink() {
+        fs::canonicalize(path)?
+    } else {
+        path.to_path_buf()
+    };
+
+    let file_name = path
+        .file_name()
+        .context("Output path has no file name")?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.themer.tmp", file_name));
+
+    let result = fs::write(&temp_path, contents)
+        .and_then(|()| match fs::metadata(&path) {
+            Ok(metadata) => fs::set_permissions(&temp_path, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|()| fs::rename(&temp_path, &path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::models::{Mode, Target};
+    use std::collections::BTreeMap;
+    use std::env;
+    use tempfile::TempDir;
+
+    fn create_empty_palette() -> Palette {
+        Palette {
+            name: "test-palette".to_string(),
+            base: None,
+            base_16: None,
+            base_30: None,
+            colors: None,
+        }
+    }
+
+    fn setup_replace_target(backup: bool) -> (TempDir, TargetProcessor, Target, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("templates");
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(templates_dir.join("colors.conf"), "color {{ color }}").unwrap();
+
+        let output_path = temp_dir.path().join("colors.conf");
+        let target = Target {
+            name: "test".to_string(),
+            template: "colors.conf".to_string(),
+            output: output_path.display().to_string(),
+            mode: Mode::Replace,
+            backup,
+            header: false,
+            ..Default::default()
+        };
+
+        let processor = TargetProcessor::new(temp_dir.path());
+        (temp_dir, processor, target, output_path)
+    }
+
+    fn color_context() -> Context {
+        let mut context = Context::new();
+        context.insert("color", "ff0000");
+        context
+    }
+
+    #[test]
+    fn test_resolve_output_path_include_mode() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+
+        let target = Target {
+            name: "test".to_string(),
+            template: "test.conf".to_string(),
+            output: String::new(),
+            mode: Mode::Include,
+            reload_cmd: String::new(),
+            ..Default::default()
+        };
+
+        let result = processor.resolve_output_path(&target);
+        assert!(result.is_ok());
+
+        let path = result.unwrap();
+        assert!(path.to_string_lossy().contains("test.conf"));
+    }
+
+    #[test]
+    fn test_generated_filename_strips_template_extension() {
+        let target = |template: &str| Target {
+            name: "kitty".to_string(),
+            template: template.to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(generated_filename(&target("kitty.conf")), "kitty.conf");
+        assert_eq!(generated_filename(&target("kitty.conf.tmpl")), "kitty.conf");
+        assert_eq!(generated_filename(&target("colors.css.tera")), "kitty.css");
+        assert_eq!(generated_filename(&target("sub/theme.lua.j2")), "kitty.lua");
+        assert_eq!(generated_filename(&target("kitty.tmpl")), "kitty");
+        assert_eq!(generated_filename(&target("kittyrc")), "kitty");
+    }
+
+    #[test]
+    fn test_resolve_output_path_include_mode_double_extension() {
+        let processor = TargetProcessor::new(&env::temp_dir()).with_cache_dir("/tmp/themer-cache");
+
+        let target = Target {
+            name: "kitty".to_string(),
+            template: "kitty.conf.tmpl".to_string(),
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+        assert_eq!(path, PathBuf::from("/tmp/themer-cache/kitty.conf"));
+    }
+
+    #[test]
+    fn test_resolve_output_path_include_mode_defaults_to_system_cache() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir).with_cache_dir("");
+
+        let target = Target {
+            name: "waybar".to_string(),
+            template: "waybar.css".to_string(),
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+        let expected = dirs::cache_dir().unwrap().join("themer").join("waybar.css");
+        assert_eq!(path, expected);
+    }
+
+    #[test]
+    fn test_resolve_output_path_include_mode_custom_cache_dir() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir).with_cache_dir("/run/user/1000/themer");
+
+        let target = Target {
+            name: "waybar".to_string(),
+            template: "waybar.css".to_string(),
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+        assert_eq!(path, PathBuf::from("/run/user/1000/themer/waybar.css"));
+    }
+
+    #[test]
+    fn test_resolve_output_path_expands_env_vars() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+        let target = Target {
+            name: "foot".to_string(),
+            template: "foot.ini".to_string(),
+            output: "$HOME/.config/foot/colors.ini".to_string(),
+            mode: Mode::Replace,
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+
+        assert_eq!(
+            path,
+            PathBuf::from(env::var("HOME").unwrap()).join(".config/foot/colors.ini")
+        );
+    }
+
+    #[test]
+    fn test_resolve_output_path_reports_undefined_var() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+        let target = Target {
+            name: "foot".to_string(),
+            template: "foot.ini".to_string(),
+            output: "${THEMER_TEST_UNDEFINED_VAR}/colors.ini".to_string(),
+            mode: Mode::Replace,
+            ..Default::default()
+        };
+
+        let err = processor.resolve_output_path(&target).unwrap_err();
+
+        assert!(err.to_string().contains("Failed to expand"));
+        assert!(format!("{:#}", err).contains("THEMER_TEST_UNDEFINED_VAR"));
+    }
+
+    #[test]
+    fn test_with_cache_dir_expands_tilde() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir).with_cache_dir("~/themer-cache");
+
+        let target = Target {
+            name: "kitty".to_string(),
+            template: "kitty.conf".to_string(),
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+        assert_eq!(
+            path,
+            dirs::home_dir()
+                .unwrap()
+                .join("themer-cache")
+                .join("kitty.conf")
+        );
+    }
+
+    #[test]
+    fn test_resolve_output_path_with_output_dir_override() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir).with_output_dir(PathBuf::from("/scratch"));
+
+        let target = Target {
+            name: "alacritty".to_string(),
+            template: "alacritty.toml".to_string(),
+            output: "~/.config/alacritty/colors.toml".to_string(),
+            mode: Mode::Replace,
+            ..Default::default()
+        };
+
+        let path = processor.resolve_output_path(&target).unwrap();
+        assert_eq!(path, PathBuf::from("/scratch/alacritty.toml"));
+    }
+
+    #[test]
+    fn test_resolve_output_path_replace_mode_empty_output() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+
+        let target = Target {
+            name: "test".to_string(),
+            template: "test.conf".to_string(),
+            output: String::new(),
+            mode: Mode::Replace,
+            reload_cmd: String::new(),
+            ..Default::default()
+        };
+
+        let result = processor.resolve_output_path(&target);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("requires an 'output' field")
+        );
+    }
+
+    #[test]
+    fn test_handle_reload_command_with_theme_placeholder() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+
+        let result = processor.handle_reload_command(
+            "echo {theme}",
+            "test",
+            "my-theme",
+            Path::new("/tmp/test.conf"),
+            Duration::from_secs(10),
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_expand_reload_placeholders() {
+        let output_path = Path::new("/home/user/.config/waybar/colors.css");
+
+        let expand = |cmd| expand_reload_placeholders(cmd, "waybar", "gruvbox", output_path);
+
+        assert_eq!(expand("echo {theme}"), "echo gruvbox");
+        assert_eq!(expand("echo {palette}"), "echo gruvbox");
+        assert_eq!(expand("echo {target}"), "echo waybar");
+        assert_eq!(
+            expand("echo {output}"),
+            "echo /home/user/.config/waybar/colors.css"
+        );
+        assert_eq!(
+            expand("reload.sh {target} {palette} {output}"),
+            "reload.sh waybar gruvbox /home/user/.config/waybar/colors.css"
+        );
+    }
+
+    #[test]
+    fn test_expand_reload_placeholders_without_placeholders() {
+        let result =
+            expand_reload_placeholders("pkill -SIGUSR2 waybar", "waybar", "nord", Path::new("x"));
+
+        assert_eq!(result, "pkill -SIGUSR2 waybar");
+    }
+
+    #[test]
+    fn test_execute_foreground_command_kills_on_timeout() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+
+        let started = Instant::now();
+        let completed = processor
+            .execute_foreground_command("sleep 30", Duration::from_millis(200))
+            .unwrap();
+
+        assert!(!completed);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_foreground_command_timeout_kills_children() {
+        let temp_dir = TempDir::new().unwrap();
+        let processor = TargetProcessor::new(temp_dir.path());
+        let pid_file = temp_dir.path().join("child.pid");
+
+        let command = format!("sleep 30 & echo $! > {}; wait", pid_file.display());
+        let completed = processor
+            .execute_foreground_command(&command, Duration::from_millis(500))
+            .unwrap();
+        assert!(!completed);
+
+        let pid = fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        // A killed child can linger briefly as a zombie until it is reaped.
+        let running = || {
+            let state = Command::new("ps")
+                .args(["-o", "stat=", "-p", &pid])
+                .output()
+                .unwrap();
+            let state = String::from_utf8_lossy(&state.stdout);
+            !state.trim().is_empty() && !state.trim_start().starts_with('Z')
+        };
+        let started = Instant::now();
+        while running() && started.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(!running(), "background child {} survived the timeout", pid);
+    }
+
+    #[test]
+    fn test_render_reuses_shared_template() {
+        let (temp_dir, mut processor, first, _) = setup_replace_target(false);
+        let second = Target {
+            name: "other".to_string(),
+            template: first.template.clone(),
+            output: first.output.clone(),
+            mode: Mode::Replace,
+            ..Default::default()
+        };
+
+        let first_render = processor.render(&first, &color_context()).unwrap();
+        fs::remove_file(temp_dir.path().join("templates").join("colors.conf")).unwrap();
+        let second_render = processor.render(&second, &color_context()).unwrap();
+
+        assert_eq!(first_render, "color ff0000");
+        assert_eq!(first_render, second_render);
+    }
+
+    #[test]
+    fn test_render_target_vars_do_not_leak_between_targets() {
+        let (temp_dir, mut processor, _, _) = setup_replace_target(false);
+        fs::write(
+            temp_dir.path().join("templates").join("term.conf"),
+            r#"opacity {{ opacity | default(value="1.0") }}"#,
+        )
+        .unwrap();
+
+        let kitty = Target {
+            name: "kitty".to_string(),
+            template: "term.conf".to_string(),
+            vars: BTreeMap::from([("opacity".to_string(), "0.9".to_string())]),
+            ..Default::default()
+        };
+        let alacritty = Target {
+            name: "alacritty".to_string(),
+            template: "term.conf".to_string(),
+            ..Default::default()
+        };
+        let context = color_context();
+
+        assert_eq!(processor.render(&kitty, &context).unwrap(), "opacity 0.9");
+        assert_eq!(
+            processor.render(&alacritty, &context).unwrap(),
+            "opacity 1.0"
+        );
+        assert!(context.get("opacity").is_none());
+    }
+
+    #[test]
+    fn test_render_resolves_partials_from_templates_dir() {
+        let (temp_dir, mut processor, target, _) = setup_replace_target(false);
+        let templates_dir = temp_dir.path().join("templates");
+        fs::create_dir_all(templates_dir.join("partials")).unwrap();
+        fs::write(
+            templates_dir.join("partials").join("color.tmpl"),
+            "shared {{ color }}",
+        )
+        .unwrap();
+        fs::write(
+            templates_dir.join(&target.template),
+            "{% include \"partials/color.tmpl\" %}",
+        )
+        .unwrap();
+
+        let rendered = processor.render(&target, &color_context()).unwrap();
+
+        assert_eq!(rendered, "shared ff0000");
+    }
+
+    #[test]
+    fn test_process_backs_up_existing_file() {
+        let (_temp, mut processor, target, output_path) = setup_replace_target(true);
+        fs::write(&output_path, "original contents").unwrap();
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        let backup_path = output_path.with_file_name("colors.conf.themer.bak");
+        assert_eq!(
+            fs::read_to_string(backup_path).unwrap(),
+            "original contents"
+        );
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "color ff0000");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_applies_mode_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_temp, mut processor, mut target, output_path) = setup_replace_target(false);
+        target.mode_bits = Some("0755".to_string());
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        let mode = fs::metadata(&output_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o7777, 0o755);
+    }
+
+    #[test]
+    fn test_parse_mode_bits() {
+        assert_eq!(parse_mode_bits("0755").unwrap(), 0o755);
+        assert_eq!(parse_mode_bits("600").unwrap(), 0o600);
+        assert_eq!(parse_mode_bits("0o4755").unwrap(), 0o4755);
+
+        for invalid in ["", "0o", "0789", "rwx", "17777"] {
+            let err = parse_mode_bits(invalid).unwrap_err().to_string();
+            assert!(err.contains("Invalid mode_bits"), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_template_parts_single_template_is_unchanged() {
+        let target = Target {
+            name: "kitty".to_string(),
+            template: "kitty.conf".to_string(),
+            ..Default::default()
+        };
+
+        let parts = template_parts(&target);
+
+        assert!(matches!(parts.as_slice(), [Cow::Borrowed(_)]));
+
+        let listed = Target {
+            template: String::new(),
+            templates: vec!["kitty.conf".to_string()],
+            ..target
+        };
+        let parts = template_parts(&listed);
+        assert_eq!(parts[0].name, "kitty");
+        assert_eq!(parts[0].template, "kitty.conf");
+    }
+
+    #[test]
+    fn test_template_parts_split_output_into_directory() {
+        let target = Target {
+            name: "alacritty".to_string(),
+            template: "alacritty/colors.toml.tmpl".to_string(),
+            templates: vec!["font.toml".to_string()],
+            output: "~/.config/alacritty/themer".to_string(),
+            mode: Mode::Replace,
+            ..Default::default()
+        };
+
+        let parts = template_parts(&target);
+
+        let summary: Vec<(&str, &str, &str)> = parts
+            .iter()
+            .map(|p| (p.name.as_str(), p.template.as_str(), p.output.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "alacritty-colors",
+                    "alacritty/colors.toml.tmpl",
+                    "~/.config/alacritty/themer/colors.toml"
+                ),
+                (
+                    "alacritty-font",
+                    "font.toml",
+                    "~/.config/alacritty/themer/font.toml"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_multiple_templates_writes_each_and_reloads_once() {
+        let (temp_dir, mut processor, mut target, _) = setup_replace_target(false);
+        let templates_dir = temp_dir.path().join("templates");
+        fs::write(templates_dir.join("font.conf.tmpl"), "font mono").unwrap();
+        let output_dir = temp_dir.path().join("out");
+        let log = temp_dir.path().join("reload.log");
+        target.templates = vec!["font.conf.tmpl".to_string()];
+        target.output = output_dir.display().to_string();
+        target.reload_cmd = format!("echo {{output}} >> {}", log.display());
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output_dir.join("colors.conf")).unwrap(),
+            "color ff0000"
+        );
+        assert_eq!(
+            fs::read_to_string(output_dir.join("font.conf")).unwrap(),
+            "font mono"
+        );
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            format!("{}\n", output_dir.display())
+        );
+    }
+
+    #[test]
+    fn test_process_multiple_templates_include_mode_names_files_apart() {
+        let (temp_dir, processor, mut target, _) = setup_replace_target(false);
+        let cache_dir = temp_dir.path().join("cache");
+        let mut processor = processor.with_cache_dir(&cache_dir.display().to_string());
+        fs::write(temp_dir.path().join("templates").join("font.conf"), "font").unwrap();
+        target.mode = Mode::Include;
+        target.output = String::new();
+        target.templates = vec!["font.conf".to_string()];
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        assert!(cache_dir.join("test-colors.conf").is_file());
+        assert!(cache_dir.join("test-font.conf").is_file());
+    }
+
+    #[test]
+    fn test_process_target_without_template_fails() {
+        let (_temp, mut processor, mut target, _) = setup_replace_target(false);
+        target.template = String::new();
+
+        let err = processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "Target 'test' has no template");
+    }
+
+    #[test]
+    fn test_process_without_backup_flag_skips_backup() {
+        let (_temp, mut processor, target, output_path) = setup_replace_target(false);
+        fs::write(&output_path, "original contents").unwrap();
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        assert!(
+            !output_path
+                .with_file_name("colors.conf.themer.bak")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_process_writes_atomically_without_leftovers() {
+        let (temp_dir, mut processor, target, output_path) = setup_replace_target(false);
+        fs::write(
+            &output_path,
+            "a much longer original file that should be fully replaced",
+        )
+        .unwrap();
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(&output_path).unwrap(), "color ff0000");
+
+        let leftovers: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_symlink_mode_links_output_to_cache() {
+        let (temp_dir, mut processor, _, _) = setup_replace_target(false);
+        let cache_dir = temp_dir.path().join("cache");
+        processor = processor.with_cache_dir(&cache_dir.display().to_string());
+        let link_path = temp_dir.path().join("config").join("colors.conf");
+
+        let target = Target {
+            name: "app".to_string(),
+            template: "colors.conf".to_string(),
+            output: link_path.display().to_string(),
+            mode: Mode::Symlink,
+            header: false,
+            ..Default::default()
+        };
+
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+        // The cached output is unchanged, but a missing link is recreated
+        fs::remove_file(&link_path).unwrap();
+        processor
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+
+        assert!(link_path.is_symlink());
+        assert_eq!(
+            fs::read_link(&link_path).unwrap(),
+            cache_dir.join("app.conf")
+        );
+        assert_eq!(fs::read_to_string(&link_path).unwrap(), "color ff0000");
+    }
+
+    #[test]
+    fn test_resolve_output_path_symlink_mode_requires_output() {
+        let temp_dir = env::temp_dir();
+        let processor = TargetProcessor::new(&temp_dir);
+
+        let target = Target {
+            name: "test".to_string(),
+            template: "test.conf".to_string(),
+            mode: Mode::Symlink,
+            ..Default::default()
+        };
+
+        let result = processor.resolve_output_path(&target);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("requires an 'output' field")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_preserves_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_path = temp_dir.path().join("real.conf");
+        let link_path = temp_dir.path().join("link.conf");
+        fs::write(&real_path, "old").unwrap();
+        std::os::unix::fs::symlink(&real_path, &link_path).unwrap();
+
+        write_atomic(&link_path, "new").unwrap();
+
+        assert!(link_path.is_symlink());
+        assert_eq!(fs::read_to_string(&real_path).unwrap(), "new");
+    }
+
+    fn process_with_header(template: &str, header: bool) -> String {
+        let temp_dir = TempDir::new().unwrap();
+        let templates_dir = temp_dir.path().join("templates");
+        fs::create_dir_all(&templates_dir).unwrap();
+        fs::write(templates_dir.join(template), "color {{ color }}\n").unwrap();
+
+        let output_path = temp_dir.path().join(template);
+        let target = Target {
+            name: "test".to_string(),
+            template: template.to_string(),
+            output: output_path.display().to_string(),
+            mode: Mode::Replace,
+            header,
+            ..Default::default()
+        };
+
+        TargetProcessor::new(temp_dir.path())
+            .process(&target, &color_context(), &create_empty_palette())
+            .unwrap();
+        fs::read_to_string(output_path).unwrap()
+    }
+
+    #[test]
+    fn test_process_header_uses_lua_comments() {
+        assert_eq!(
+            process_with_header("colors.lua", true),
+            "-- DO NOT EDIT — generated by themer\ncolor ff0000\n"
+        );
+    }
+
+    #[test]
+    fn test_process_header_uses_hash_comments_for_conf() {
+        assert_eq!(
+            process_with_header("colors.conf", true),
+            "# DO NOT EDIT — generated by themer\ncolor ff0000\n"
+        );
+    }
+
+    #[test]
+    fn test_process_header_skipped_for_unknown_format_or_when_disabled() {
+        assert_eq!(process_with_header("colors.json", true), "color ff0000\n");
+        assert_eq!(process_with_header("colors.conf", false), "color ff0000\n");
+    }
+
+    #[test]
+    fn test_with_header_keeps_shebang_first() {
+        let rendered = with_header("#!/bin/sh\necho hi\n", Path::new("colors.sh"));
+
+        assert_eq!(
+            rendered,
+            "#!/bin/sh\n# DO NOT EDIT — generated by themer\necho hi\n"
+        );
+    }
+
+    #[test]
+    fn test_with_header_css_uses_block_comment() {
+        let rendered = with_header("a {}\n", Path::new("waybar.css"));
+
+        assert_eq!(rendered, "/* DO NOT EDIT — generated by themer */\na {}\n");
+    }
+
+    #[test]
+    fn test_unknown_placeholders_accepts_known() {
+        let command = "kitty @ set-colors {output} && notify-send {theme} {palette} {target}";
+
+        assert!(unknown_placeholders(command, &RELOAD_PLACEHOLDERS).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_placeholders_flags_typos() {
+        let command = "notify-send {them} && echo {output} {palete}";
+
+        assert_eq!(
+            unknown_placeholders(command, &RELOAD_PLACEHOLDERS),
+            vec!["{them}", "{palete}"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholders_ignores_shell_syntax() {
+        let command = "echo ${HOME} && awk '{print $1}' && f() { true; }";
+
+        assert!(unknown_placeholders(command, &RELOAD_PLACEHOLDERS).is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_problems_checks_reload_and_output() {
+        let target = Target {
+            name: "kitty".to_string(),
+            output: "~/.config/kitty/{theme}.conf".to_string(),
+            reload_cmd: "pkill -USR1 {targte}".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            placeholder_problems(&target),
+            vec![
+                "Target 'kitty' reload_cmd has unknown placeholder '{targte}'",
+                "Target 'kitty' output has unresolved placeholder '{theme}'",
+            ]
+        );
+    }
+}
@@ -1,9 +1,24 @@
-use anyhow::Result;
-use tera::{Context, Tera};
+use anyhow::{Context as _, Result};
+use chrono::{Local, SecondsFormat};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tera::{Context, Tera, Value};
 
 use super::filters;
+use crate::config::models::Config;
+use crate::palette::loader::PaletteLoader;
 use crate::palette::models::Palette;
 
+/// Context key holding the ordered base16 colors that back `palette_colors()`
+/// and `nearest()`. Tera functions can't read the render context, so both
+/// functions are rebound to these colors before every render.
+const PALETTE_COLORS_KEY: &str = "__palette_colors";
+
+/// Subdirectory of the templates directory holding shared snippets that
+/// templates pull in with `{% include "partials/<file>" %}`.
+pub const PARTIALS_DIR: &str = "partials";
+
 pub struct TemplateEngine {
     tera: Tera,
 }
@@ -20,16 +35,46 @@ impl TemplateEngine {
 
         tera.register_filter("hex_hash", filters::hex_hash);
         tera.register_filter("rgb", filters::rgb);
-
-        Self { tera }
+        tera.register_filter("contrast_color", filters::contrast_color);
+        tera.register_filter("rotate_hue", filters::rotate_hue);
+        tera.register_filter("alpha", filters::alpha);
+        tera.register_filter("strip_hash", filters::strip_hash);
+        tera.register_filter("bgr", filters::bgr);
+        tera.register_filter("int", filters::int);
+        tera.register_filter("rgb_components", filters::rgb_components);
+        tera.register_filter("luminance", filters::luminance);
+        tera.register_filter("is_dark", filters::is_dark);
+        tera.register_filter("simulate_cvd", filters::simulate_cvd);
+        tera.register_filter("contrast_adjust", filters::contrast_adjust);
+        tera.register_filter("web_safe", filters::web_safe);
+        tera.register_filter("blend_over", filters::blend_over);
+        tera.register_filter("ansi256", filters::ansi256);
+        tera.register_filter("short_hex", filters::short_hex);
+        tera.register_filter("tint", filters::tint);
+        tera.register_filter("shade", filters::shade);
+        tera.register_filter("fallback", filters::fallback);
+        tera.register_function("contrast_ratio", filters::contrast_ratio);
+        tera.register_function("gradient", filters::gradient);
+        tera.register_function("scheme", filters::scheme);
+
+        let mut engine = Self { tera };
+        engine.bind_palette_colors(None);
+        engine
     }
 
     pub fn create_context(&self, palette: &Palette) -> Result<Context> {
         let mut context = Context::new();
 
         context.insert("name", &palette.name);
+        context.insert("palette_name", &palette.name);
+        context.insert(
+            "generated_at",
+            &Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+        );
+        context.insert("themer_version", env!("CARGO_PKG_VERSION"));
 
         let base16 = palette.base16()?;
+        context.insert(PALETTE_COLORS_KEY, &base16.colors().collect::<Vec<_>>());
 
         macro_rules! insert_colors {
             ($($field:ident),+ $(,)?) => {
@@ -48,56 +93,189 @@ impl TemplateEngine {
         context.insert("base0E", &base16.base0e);
         context.insert("base0F", &base16.base0f);
 
-        if let Ok(base30) = palette.base30() {
-            macro_rules! insert_base30 {
-                ($($field:ident),+ $(,)?) => {
-                    $(context.insert(stringify!($field), &base30.$field);)+
-                };
+        // Palettes that only ship base16 still expose base30 names, derived
+        // from the base16 colors.
+        let derived;
+        let base30 = match palette.base30() {
+            Ok(base30) => base30,
+            Err(_) => {
+                derived = base16.to_base30();
+                &derived
             }
+        };
+
+        macro_rules! insert_base30 {
+            ($($field:ident),+ $(,)?) => {
+                $(context.insert(stringify!($field), &base30.$field);)+
+            };
+        }
+
+        insert_base30!(
+            white,
+            darker_black,
+            black,
+            black2,
+            one_bg,
+            one_bg2,
+            one_bg3,
+            grey,
+            grey_fg,
+            grey_fg2,
+            light_grey,
+            red,
+            baby_pink,
+            pink,
+            line,
+            green,
+            vibrant_green,
+            nord_blue,
+            blue,
+            yellow,
+            sun,
+            purple,
+            dark_purple,
+            teal,
+            orange,
+            cyan,
+            lightbg,
+        );
 
-            insert_base30!(
-                white,
-                darker_black,
-                black,
-                black2,
-                one_bg,
-                one_bg2,
-                one_bg3,
-                grey,
-                grey_fg,
-                grey_fg2,
-                light_grey,
-                red,
-                baby_pink,
-                pink,
-                line,
-                green,
-                vibrant_green,
-                nord_blue,
-                blue,
-                yellow,
-                sun,
-                purple,
-                dark_purple,
-                teal,
-                orange,
-                cyan,
-                lightbg,
-            );
+        for (key, color) in palette.colors.iter().flatten() {
+            context.insert(format!("custom_{}", key), color);
         }
 
         Ok(context)
     }
 
+    /// The context for rendering targets as if `palette_name` were applied:
+    /// [`create_context_with`](Self::create_context_with) plus `prev_*` for
+    /// the palette it replaces, i.e. the one recorded as previous once it is
+    /// applied. `prev_*` is only added when that palette loads.
+    pub fn create_target_context(
+        &self,
+        palette: &Palette,
+        palette_name: &str,
+        config: &Config,
+        palette_loader: &PaletteLoader,
+    ) -> Result<Context> {
+        let mut context = self.create_context_with(palette, config)?;
+
+        let previous_name = if config.active_palette != palette_loader.resolve(palette_name) {
+            &config.active_palette
+        } else {
+            &config.previous_palette
+        };
+        if !previous_name.is_empty()
+            && let Ok(previous) = palette_loader.load(previous_name)
+        {
+            self.add_previous_palette(&mut context, &previous)?;
+        }
+
+        Ok(context)
+    }
+
+    /// Adds the colors and name of `previous` to `context`, each prefixed
+    /// with `prev_` (`prev_base00`, `prev_red`, `prev_name`, ...), for
+    /// templates that transition from one palette to the next.
+    pub fn add_previous_palette(&self, context: &mut Context, previous: &Palette) -> Result<()> {
+        let previous_context = self.create_context(previous)?;
+
+        if let serde_json::Value::Object(vars) = previous_context.into_json() {
+            for (key, value) in vars {
+                if matches!(
+                    key.as_str(),
+                    PALETTE_COLORS_KEY | "generated_at" | "themer_version"
+                ) {
+                    continue;
+                }
+                context.insert(format!("prev_{}", key), &value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The context targets render with: [`create_context`](Self::create_context)
+    /// plus the display preferences from `config.toml` (`transparency` and
+    /// `opacity`).
+    pub fn create_context_with(&self, palette: &Palette, config: &Config) -> Result<Context> {
+        let mut context = self.create_context(palette)?;
+        context.insert("transparency", &config.transparency);
+        context.insert("opacity", &config.opacity);
+        Ok(context)
+    }
+
     pub fn render(
         &mut self,
         template_name: &str,
         template_content: &str,
         context: &Context,
     ) -> Result<String> {
+        self.add_template(template_name, template_content)?;
+        self.render_template(template_name, context)
+    }
+
+    /// Parses and registers a template so it can be rendered repeatedly with
+    /// [`render_template`](Self::render_template).
+    pub fn add_template(&mut self, template_name: &str, template_content: &str) -> Result<()> {
         self.tera
             .add_raw_template(template_name, template_content)?;
-        Ok(self.tera.render(template_name, context)?)
+        Ok(())
+    }
+
+    /// Registers every file under `<templates_dir>/partials/` (recursively)
+    /// under its path relative to `templates_dir`, e.g. `partials/base.tmpl`,
+    /// so templates can include them. Does nothing if the directory is missing.
+    pub fn load_partials(&mut self, templates_dir: &Path) -> Result<()> {
+        let partials_dir = templates_dir.join(PARTIALS_DIR);
+        if !partials_dir.is_dir() {
+            return Ok(());
+        }
+
+        let mut partials = Vec::new();
+        collect_partials(&partials_dir, PARTIALS_DIR, &mut partials)?;
+        self.tera
+            .add_raw_templates(partials)
+            .context("Failed to load partials")?;
+        Ok(())
+    }
+
+    pub fn has_template(&self, template_name: &str) -> bool {
+        self.tera
+            .get_template_names()
+            .any(|name| name == template_name)
+    }
+
+    /// Renders a registered template. When it uses an undefined variable
+    /// that is a near miss of a context key (e.g. `base0G`), the error
+    /// suggests that key on top of Tera's own message.
+    pub fn render_template(&mut self, template_name: &str, context: &Context) -> Result<String> {
+        self.bind_palette_colors(context.get(PALETTE_COLORS_KEY).cloned());
+        self.tera
+            .render(template_name, context)
+            .map_err(|e| match suggest_variable(&e, context) {
+                Some(hint) => anyhow::Error::new(e).context(hint),
+                None => e.into(),
+            })
+    }
+
+    fn bind_palette_colors(&mut self, colors: Option<Value>) {
+        let palette: Vec<Value> = colors
+            .as_ref()
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        self.tera
+            .register_function("nearest", move |args: &HashMap<String, Value>| {
+                filters::nearest(&palette, args)
+            });
+
+        self.tera
+            .register_function("palette_colors", move |_args: &HashMap<String, Value>| {
+                colors
+                    .clone()
+                    .ok_or_else(|| tera::Error::msg("palette_colors() requires a palette"))
+            });
     }
 
     pub fn render_palette(
@@ -111,10 +289,80 @@ impl TemplateEngine {
     }
 }
 
+/// Builds a "did you mean" hint when `error` is Tera's undefined-variable
+/// error and a context key is within a couple of edits of the name.
+fn suggest_variable(error: &tera::Error, context: &Context) -> Option<String> {
+    let mut cause: Option<&dyn std::error::Error> = Some(error);
+    let name = std::iter::from_fn(|| {
+        let current = cause?;
+        cause = current.source();
+        Some(current.to_string())
+    })
+    .find_map(|message| {
+        let rest = message.split_once("Variable `")?.1;
+        Some(rest.split_once("` not found")?.0.to_string())
+    })?;
+
+    let serde_json::Value::Object(vars) = context.clone().into_json() else {
+        return None;
+    };
+    let max_distance = (name.chars().count() / 3).clamp(1, 2);
+
+    vars.keys()
+        .filter(|key| key.as_str() != PALETTE_COLORS_KEY)
+        .map(|key| (edit_distance(&name, key), key))
+        .filter(|&(distance, _)| distance <= max_distance)
+        // On a tie, a key differing only in case (`base0d` → `base0D`) wins.
+        .min_by_key(|&(distance, key)| (distance, !key.eq_ignore_ascii_case(&name)))
+        .map(|(_, key)| format!("unknown variable `{}`; did you mean `{}`?", name, key))
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+fn collect_partials(dir: &Path, prefix: &str, partials: &mut Vec<(String, String)>) -> Result<()> {
+    let entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(filename) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let name = format!("{}/{}", prefix, filename);
+
+        if path.is_dir() {
+            collect_partials(&path, &name, partials)?;
+        } else {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read partial: {}", path.display()))?;
+            partials.push((name, content));
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::palette::models::{Base16, Base30, Palette};
+    use std::collections::BTreeMap;
 
     fn create_minimal_base16() -> Base16 {
         Base16 {
@@ -172,24 +420,30 @@ mod tests {
     fn create_test_palette_base16_only() -> Palette {
         Palette {
             name: "test-palette".to_string(),
+            base: None,
             base_16: Some(create_minimal_base16()),
             base_30: None,
+            colors: None,
         }
     }
 
     fn create_test_palette_full() -> Palette {
         Palette {
             name: "full-palette".to_string(),
+            base: None,
             base_16: Some(create_minimal_base16()),
             base_30: Some(create_minimal_base30()),
+            colors: None,
         }
     }
 
     fn create_empty_palette() -> Palette {
         Palette {
             name: "empty".to_string(),
+            base: None,
             base_16: None,
             base_30: None,
+            colors: None,
         }
     }
 
@@ -198,6 +452,24 @@ mod tests {
         let engine = TemplateEngine::new();
         assert!(engine.tera.get_filter("hex_hash").is_ok());
         assert!(engine.tera.get_filter("rgb").is_ok());
+        assert!(engine.tera.get_filter("contrast_color").is_ok());
+        assert!(engine.tera.get_filter("rotate_hue").is_ok());
+        assert!(engine.tera.get_filter("alpha").is_ok());
+        assert!(engine.tera.get_filter("strip_hash").is_ok());
+        assert!(engine.tera.get_filter("bgr").is_ok());
+        assert!(engine.tera.get_filter("int").is_ok());
+        assert!(engine.tera.get_filter("rgb_components").is_ok());
+        assert!(engine.tera.get_filter("luminance").is_ok());
+        assert!(engine.tera.get_filter("is_dark").is_ok());
+        assert!(engine.tera.get_filter("simulate_cvd").is_ok());
+        assert!(engine.tera.get_filter("contrast_adjust").is_ok());
+        assert!(engine.tera.get_filter("web_safe").is_ok());
+        assert!(engine.tera.get_filter("blend_over").is_ok());
+        assert!(engine.tera.get_filter("ansi256").is_ok());
+        assert!(engine.tera.get_filter("short_hex").is_ok());
+        assert!(engine.tera.get_filter("tint").is_ok());
+        assert!(engine.tera.get_filter("shade").is_ok());
+        assert!(engine.tera.get_filter("fallback").is_ok());
     }
 
     #[test]
@@ -225,6 +497,80 @@ mod tests {
         assert_eq!(context.get("base0F").unwrap().as_str(), Some("ffffff"));
     }
 
+    #[test]
+    fn test_create_context_includes_metadata() {
+        let engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let context = engine
+            .create_context(&palette)
+            .expect("Context creation failed");
+
+        assert_eq!(
+            context.get("palette_name").unwrap().as_str(),
+            Some("test-palette")
+        );
+        assert_eq!(
+            context.get("themer_version").unwrap().as_str(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+
+        let generated_at = context.get("generated_at").unwrap().as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(generated_at).is_ok());
+    }
+
+    #[test]
+    fn test_create_context_metadata_does_not_shadow_colors() {
+        let palette = create_test_palette_full();
+        let base16 = serde_json::to_value(palette.base16().unwrap()).unwrap();
+        let base30 = serde_json::to_value(palette.base30().unwrap()).unwrap();
+
+        for key in ["palette_name", "generated_at", "themer_version"] {
+            assert!(base16.get(key).is_none(), "{} collides with base16", key);
+            assert!(base30.get(key).is_none(), "{} collides with base30", key);
+        }
+    }
+
+    #[test]
+    fn test_add_previous_palette_prefixes_colors() {
+        let engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+        let mut previous = create_test_palette_full();
+        previous.base_16.as_mut().unwrap().base00 = "282828".to_string();
+
+        let plain = engine.create_context(&palette).unwrap();
+        let mut context = engine.create_context(&palette).unwrap();
+        engine
+            .add_previous_palette(&mut context, &previous)
+            .unwrap();
+
+        assert!(plain.get("prev_base00").is_none());
+        assert_eq!(context.get("prev_base00").unwrap(), "282828");
+        assert_eq!(context.get("prev_base0A").unwrap(), "aaaaaa");
+        assert_eq!(context.get("prev_name").unwrap(), "full-palette");
+        assert_eq!(context.get("base00").unwrap(), "000000");
+        assert!(context.get("prev_themer_version").is_none());
+    }
+
+    #[test]
+    fn test_create_context_with_settings() {
+        let engine = TemplateEngine::new();
+        let config = Config {
+            transparency: true,
+            opacity: 0.85,
+            ..Default::default()
+        };
+
+        let context = engine
+            .create_context_with(&create_test_palette_base16_only(), &config)
+            .unwrap();
+
+        assert_eq!(context.get("base00").unwrap(), "000000");
+
+        assert_eq!(context.get("transparency").unwrap(), true);
+        assert_eq!(context.get("opacity").unwrap(), 0.85);
+    }
+
     #[test]
     fn test_create_context_with_base30() {
         let engine = TemplateEngine::new();
@@ -240,6 +586,20 @@ mod tests {
         assert_eq!(context.get("lightbg").unwrap().as_str(), Some("eeeeee"));
     }
 
+    #[test]
+    fn test_create_context_derives_base30_from_base16() {
+        let engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let context = engine
+            .create_context(&palette)
+            .expect("Context creation failed");
+
+        assert_eq!(context.get("red").unwrap().as_str(), Some("888888"));
+        assert_eq!(context.get("green").unwrap().as_str(), Some("bbbbbb"));
+        assert_eq!(context.get("blue").unwrap().as_str(), Some("dddddd"));
+    }
+
     #[test]
     fn test_create_context_missing_base16_returns_error() {
         let engine = TemplateEngine::new();
@@ -263,6 +623,32 @@ mod tests {
         assert_eq!(result, "Color: ff0000");
     }
 
+    #[test]
+    fn test_render_with_contrast_ratio_function() {
+        let mut engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let template = r#"{% if contrast_ratio(fg=base00, bg=base0F) > 4.5 %}ok{% endif %}"#;
+        let result = engine
+            .render_palette("contrast", template, &palette)
+            .expect("Render failed");
+
+        assert_eq!(result, "ok");
+    }
+
+    #[test]
+    fn test_render_with_luminance_conditional() {
+        let mut engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let template = r#"{% if base00 | luminance < 0.5 %}dark{% else %}light{% endif %}"#;
+        let result = engine
+            .render_palette("luminance", template, &palette)
+            .expect("Render failed");
+
+        assert_eq!(result, "dark");
+    }
+
     #[test]
     fn test_render_with_filter() {
         let mut engine = TemplateEngine::new();
@@ -291,6 +677,28 @@ mod tests {
         assert!(result.contains("Base00: 000000"));
     }
 
+    #[test]
+    fn test_render_custom_colors() {
+        let mut engine = TemplateEngine::new();
+        let mut palette = create_test_palette_base16_only();
+        palette.colors = Some(BTreeMap::from([
+            ("primary".to_string(), "ffffff".to_string()),
+            ("base00".to_string(), "123456".to_string()),
+        ]));
+
+        let context = engine.create_context(&palette).unwrap();
+        assert_eq!(context.get("custom_primary").unwrap(), "ffffff");
+
+        let result = engine
+            .render_palette(
+                "custom",
+                "{{ custom_primary | hex_hash }} {{ base00 }} {{ custom_base00 }}",
+                &palette,
+            )
+            .unwrap();
+        assert_eq!(result, "#ffffff 000000 123456");
+    }
+
     #[test]
     fn test_render_palette_with_base30() {
         let mut engine = TemplateEngine::new();
@@ -327,6 +735,51 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_render_near_miss_variable_suggests_key() {
+        let mut engine = TemplateEngine::new();
+        let context = engine
+            .create_context(&create_test_palette_base16_only())
+            .unwrap();
+
+        let err = engine
+            .render("typo", "{{ palete_name }}", &context)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown variable `palete_name`; did you mean `palette_name`?"
+        );
+        assert!(format!("{:#}", err).contains("Variable `palete_name` not found"));
+
+        let err = engine.render("case", "{{ base0d }}", &context).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown variable `base0d`; did you mean `base0D`?"
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_variable_has_no_suggestion() {
+        let mut engine = TemplateEngine::new();
+        let context = engine
+            .create_context(&create_test_palette_base16_only())
+            .unwrap();
+
+        let err = engine
+            .render("unknown", "{{ wallpaper_path }}", &context)
+            .unwrap_err();
+
+        assert!(!format!("{:#}", err).contains("did you mean"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("base0G", "base0F"), 1);
+        assert_eq!(edit_distance("bse00", "base00"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn test_multiple_renders_reuse_engine() {
         let mut engine = TemplateEngine::new();
@@ -411,6 +864,53 @@ white exists: {{ white }}
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_palette_colors_function() {
+        let mut engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let template = "{% for c in palette_colors() %}{{ c }} {% endfor %}";
+        let result = engine
+            .render_palette("swatch", template, &palette)
+            .expect("Render failed");
+
+        let colors: Vec<&str> = result.split_whitespace().collect();
+        assert_eq!(colors.len(), 16);
+        assert_eq!(colors[0], "000000");
+        assert_eq!(colors[10], "aaaaaa");
+        assert_eq!(colors[15], "ffffff");
+    }
+
+    #[test]
+    fn test_palette_colors_function_without_palette() {
+        let mut engine = TemplateEngine::new();
+
+        let result = engine.render("swatch", "{{ palette_colors() }}", &Context::new());
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_nearest_function_uses_rendered_palette() {
+        let mut engine = TemplateEngine::new();
+        let palette = create_test_palette_base16_only();
+
+        let result = engine
+            .render_palette("snap", r##"{{ nearest(color="#8a8787") }}"##, &palette)
+            .expect("Render failed");
+
+        assert_eq!(result, "888888");
+    }
+
+    #[test]
+    fn test_nearest_function_without_palette() {
+        let mut engine = TemplateEngine::new();
+
+        let result = engine.render("snap", r#"{{ nearest(color="ff0000") }}"#, &Context::new());
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_render_template_with_loops() {
         let mut engine = TemplateEngine::new();
@@ -448,4 +948,39 @@ white exists: {{ white }}
 
         assert_eq!(result, "test-palette");
     }
+
+    #[test]
+    fn test_template_includes_partial() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let partials_dir = temp_dir.path().join("partials").join("colors");
+        std::fs::create_dir_all(&partials_dir).unwrap();
+        std::fs::write(
+            temp_dir.path().join("partials").join("base.tmpl"),
+            "fg={{ base05 }}",
+        )
+        .unwrap();
+        std::fs::write(partials_dir.join("bg.tmpl"), "bg={{ base00 }}").unwrap();
+
+        let mut engine = TemplateEngine::new();
+        engine.load_partials(temp_dir.path()).unwrap();
+        let palette = create_test_palette_base16_only();
+
+        let result = engine
+            .render_palette(
+                "kitty.conf",
+                "{% include \"partials/colors/bg.tmpl\" %}\n{% include \"partials/base.tmpl\" %}",
+                &palette,
+            )
+            .unwrap();
+
+        assert_eq!(result, "bg=000000\nfg=555555");
+    }
+
+    #[test]
+    fn test_load_partials_without_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut engine = TemplateEngine::new();
+
+        assert!(engine.load_partials(temp_dir.path()).is_ok());
+    }
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::fmt;
 use tera::{Error as TeraError, Result as TeraResult, Value};
 
+use crate::palette::color::{self, Color};
+
 #[derive(Debug, Clone)]
 enum ColorFilterError {
     Type {
@@ -17,6 +19,21 @@ enum ColorFilterError {
     AlphaRange {
         value: f64,
     },
+    AmountRange {
+        value: f64,
+    },
+    MissingArgument {
+        name: &'static str,
+    },
+    StepsRange {
+        value: u64,
+    },
+    UnknownCvdType {
+        value: String,
+    },
+    UnknownSchemeKind {
+        value: String,
+    },
 }
 
 impl fmt::Display for ColorFilterError {
@@ -42,6 +59,29 @@ impl fmt::Display for ColorFilterError {
             Self::AlphaRange { value } => {
                 write!(f, "Alpha value {} must be between 0.0 and 1.0", value)
             }
+            Self::AmountRange { value } => {
+                write!(f, "Amount {} must be between 0.0 and 1.0", value)
+            }
+            Self::MissingArgument { name } => {
+                write!(f, "Missing required argument '{}'", name)
+            }
+            Self::StepsRange { value } => {
+                write!(f, "Gradient needs at least 2 steps, got {}", value)
+            }
+            Self::UnknownCvdType { value } => {
+                write!(
+                    f,
+                    "Unknown color vision deficiency type '{}', expected 'prot', 'deut' or 'trit'",
+                    value
+                )
+            }
+            Self::UnknownSchemeKind { value } => {
+                write!(
+                    f,
+                    "Unknown scheme kind '{}', expected 'complementary', 'triadic', 'analogous' or 'tetradic'",
+                    value
+                )
+            }
         }
     }
 }
@@ -54,22 +94,43 @@ impl From<ColorFilterError> for TeraError {
 
 /// Adds a '#' prefix to a hex color string.
 ///
+/// # Arguments
+///
+/// * `value` - A hex color string
+/// * `args` - Optional arguments:
+///   - `upper`: Uppercases the hex digits, defaults to false (keep the case)
+///
 /// # Examples
 ///
 /// In a Tera template:
 /// ```text
-/// {{ "FF5733" | hex_hash }}  -> "#FF5733"
+/// {{ "FF5733" | hex_hash }}              -> "#FF5733"
+/// {{ "ff5733" | hex_hash(upper=true) }}  -> "#FF5733"
 /// ```
 ///
 /// # Errors
 ///
-/// Returns an error if the input is not a string.
-pub fn hex_hash(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+/// Returns an error if the input is not a string or `upper` is not a boolean.
+pub fn hex_hash(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
     let color_str = value
         .as_str()
         .ok_or(ColorFilterError::Type { expected: "string" })?;
 
-    Ok(Value::String(format!("#{}", color_str)))
+    let upper = match args.get("upper") {
+        Some(upper) => upper.as_bool().ok_or(ColorFilterError::Type {
+            expected: "boolean",
+        })?,
+        None => false,
+    };
+
+    if upper {
+        Ok(Value::String(format!(
+            "#{}",
+            color_str.to_ascii_uppercase()
+        )))
+    } else {
+        Ok(Value::String(format!("#{}", color_str)))
+    }
 }
 
 /// Converts a hex color code to RGB or RGBA format.
@@ -103,185 +164,1889 @@ pub fn rgb(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
         return Err(ColorFilterError::AlphaRange { value: alpha }.into());
     }
 
-    let hex_str = value
-        .as_str()
-        .ok_or(ColorFilterError::Type { expected: "string" })?;
+    let color = parse_hex_color(value)?;
 
-    let hex_code = hex_str.strip_prefix('#').unwrap_or(hex_str);
+    let output = format_rgb_output(color.r, color.g, color.b, alpha);
+    Ok(Value::String(output))
+}
 
-    if hex_code.len() != 6 {
-        return Err(ColorFilterError::HexLength {
-            actual: hex_code.len(),
-        }
-        .into());
-    }
+/// Picks a legible foreground (black or white) for the given background color.
+///
+/// The candidate with the higher WCAG contrast ratio against the background wins.
+///
+/// # Arguments
+///
+/// * `value` - The background hex color (with or without '#' prefix)
+/// * `args` - Optional arguments:
+///   - `light`: Hex color used as the light candidate, defaults to "ffffff"
+///   - `dark`: Hex color used as the dark candidate, defaults to "000000"
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "1e1e2e" | contrast_color }}                -> "ffffff"
+/// {{ "f5f5f5" | contrast_color }}                -> "000000"
+/// {{ base00 | contrast_color(light=base05) }}    -> base05 on dark backgrounds
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the background or either candidate is not a valid hex color.
+pub fn contrast_color(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let background = parse_hex_color(value)?;
 
-    let r = parse_hex_component(hex_code, 0..2, "Red")?;
-    let g = parse_hex_component(hex_code, 2..4, "Green")?;
-    let b = parse_hex_component(hex_code, 4..6, "Blue")?;
+    let light = args
+        .get("light")
+        .cloned()
+        .unwrap_or_else(|| Value::String("ffffff".to_string()));
+    let dark = args
+        .get("dark")
+        .cloned()
+        .unwrap_or_else(|| Value::String("000000".to_string()));
 
-    let output = format_rgb_output(r, g, b, alpha);
-    Ok(Value::String(output))
-}
+    let light_ratio = background.contrast(parse_hex_color(&light)?);
+    let dark_ratio = background.contrast(parse_hex_color(&dark)?);
 
-#[inline]
-fn parse_hex_component(
-    hex_code: &str,
-    range: std::ops::Range<usize>,
-    component_name: &'static str,
-) -> Result<u8, TeraError> {
-    let hex_slice = &hex_code[range.clone()];
-    u8::from_str_radix(hex_slice, 16).map_err(|_| {
-        ColorFilterError::HexDigit {
-            component: component_name,
-            value: hex_slice.to_string(),
-        }
-        .into()
+    Ok(if light_ratio >= dark_ratio {
+        light
+    } else {
+        dark
     })
 }
 
-#[inline]
-fn format_rgb_output(r: u8, g: u8, b: u8, alpha: f64) -> String {
-    const EPSILON: f64 = 1e-10;
+/// Rotates the hue of a hex color by the given number of degrees.
+///
+/// Saturation and lightness are preserved; the hue wraps around modulo 360.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `degrees`: Rotation in degrees, may be negative
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | rotate_hue(degrees=120) }}   -> "00ff00"
+/// {{ base0D | rotate_hue(degrees=180) }}     -> complement of base0D
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color or `degrees` is
+/// missing or not a number.
+pub fn rotate_hue(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let degrees = required_arg(args, "degrees")?
+        .as_f64()
+        .ok_or(ColorFilterError::Type { expected: "number" })?;
 
-    if (alpha - 1.0).abs() < EPSILON {
-        format!("rgb({}, {}, {})", r, g, b)
-    } else {
-        format!("rgba({}, {}, {}, {:.2})", r, g, b, alpha)
-    }
+    Ok(Value::String(
+        parse_hex_color(value)?.rotate_hue(degrees).to_hex(),
+    ))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
+/// Appends an alpha channel to a hex color, producing 8-digit `RRGGBBAA` hex.
+///
+/// The output has no '#' prefix so it can be chained with `hex_hash`.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `a`: Alpha channel (0.0-1.0)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "1e1e2e" | alpha(a=1.0) }}              -> "1e1e2eff"
+/// {{ base00 | alpha(a=0.5) | hex_hash }}     -> "#28282880"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color, or `a` is missing,
+/// not a number, or outside [0.0, 1.0].
+pub fn alpha(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let alpha = required_alpha(args)?;
+    let color = parse_hex_color(value)?;
+    let alpha_byte = (alpha * 255.0).round() as u8;
 
-    #[test]
-    fn test_hex_hash_filter_success() {
-        let result = hex_hash(&Value::String("FF5733".to_string()), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("#FF5733"));
+    Ok(Value::String(format!(
+        "{}{:02x}",
+        color.to_hex(),
+        alpha_byte
+    )))
+}
 
-        let result = hex_hash(&json!("000000"), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("#000000"));
-    }
+/// Removes a single leading '#' from a hex color string, the inverse of
+/// `hex_hash`.
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "#FF5733" | strip_hash }}  -> "FF5733"
+/// {{ "FF5733" | strip_hash }}   -> "FF5733"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a string.
+pub fn strip_hash(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let color_str = value
+        .as_str()
+        .ok_or(ColorFilterError::Type { expected: "string" })?;
 
-    #[test]
-    fn test_hex_hash_filter_invalid_type() {
-        let result = hex_hash(&Value::Number(123.into()), &HashMap::new());
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("Invalid type"));
-    }
+    Ok(Value::String(
+        color_str.strip_prefix('#').unwrap_or(color_str).to_string(),
+    ))
+}
 
-    #[test]
-    fn test_rgb_filter_basic_colors() {
-        // Without hash prefix
-        let result = rgb(&json!("FF5733"), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("rgb(255, 87, 51)"));
+/// Re-emits a hex color with its components in blue, green, red order.
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "FF0000" | bgr }}   -> "0000ff"
+/// {{ "#123456" | bgr }}  -> "563412"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn bgr(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let Color { r, g, b } = parse_hex_color(value)?;
 
-        // With hash prefix
-        let result = rgb(&json!("#2E8B57"), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("rgb(46, 139, 87)"));
+    Ok(Value::String(Color::new(b, g, r).to_hex()))
+}
 
-        // Black and white
-        let result = rgb(&json!("000000"), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("rgb(0, 0, 0)"));
+/// Converts a hex color to the integer `0xRRGGBB`.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Optional arguments:
+///   - `prefix`: When set, returns a string of the prefix followed by the six
+///     hex digits instead of a number
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | int }}               -> 16711680
+/// {{ "ff0000" | int(prefix="0x") }}  -> "0xff0000"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color or `prefix` is not
+/// a string.
+pub fn int(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let Color { r, g, b } = parse_hex_color(value)?;
+    let color = u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b);
 
-        let result = rgb(&json!("FFFFFF"), &HashMap::new());
-        assert_eq!(result.unwrap(), json!("rgb(255, 255, 255)"));
+    match args.get("prefix") {
+        Some(prefix) => {
+            let prefix = prefix
+                .as_str()
+                .ok_or(ColorFilterError::Type { expected: "string" })?;
+            Ok(Value::String(format!("{}{:06x}", prefix, color)))
+        }
+        None => Ok(Value::from(color)),
     }
+}
 
-    #[test]
-    fn test_rgb_filter_with_alpha() {
-        let mut args = HashMap::new();
-        args.insert("a".to_string(), json!(0.5));
-        let result = rgb(&json!("4169E1"), &args);
-        assert_eq!(result.unwrap(), json!("rgba(65, 105, 225, 0.50)"));
+/// Splits a hex color into an array of its numeric `[r, g, b]` components.
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {% set c = "ff8000" | rgb_components %}{{ c[0] }} {{ c[1] }} {{ c[2] }}  -> "255 128 0"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn rgb_components(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let Color { r, g, b } = parse_hex_color(value)?;
 
-        // Alpha = 0.0
-        let mut args = HashMap::new();
-        args.insert("a".to_string(), json!(0.0));
-        let result = rgb(&json!("FFFFFF"), &args);
-        assert_eq!(result.unwrap(), json!("rgba(255, 255, 255, 0.00)"));
+    Ok(Value::Array(vec![
+        Value::from(r),
+        Value::from(g),
+        Value::from(b),
+    ]))
+}
 
-        // Alpha = 1.0 should omit alpha
-        let mut args = HashMap::new();
-        args.insert("a".to_string(), json!(1.0));
-        let result = rgb(&json!("FFFFFF"), &args);
-        assert_eq!(result.unwrap(), json!("rgb(255, 255, 255)"));
-    }
+/// Returns the WCAG relative luminance of a color, from 0.0 (black) to 1.0
+/// (white).
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ffffff" | luminance }}                         -> 1.0
+/// {% if base00 | luminance < 0.5 %}dark{% endif %}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn luminance(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    Ok(Value::from(parse_hex_color(value)?.luminance()))
+}
 
-    #[test]
-    fn test_rgb_filter_invalid_type() {
-        let result = rgb(&json!(12345), &HashMap::new());
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Invalid type"));
-    }
+/// Returns `true` when a color's WCAG relative luminance is below a threshold.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Optional arguments:
+///   - `threshold`: Luminance below which the color counts as dark, defaults
+///     to 0.5
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {% if base00 | is_dark %}dark{% endif %}
+/// {{ "777777" | is_dark(threshold=0.1) }}   -> false
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color or `threshold` is
+/// not a number.
+pub fn is_dark(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let threshold = match args.get("threshold") {
+        Some(threshold) => threshold
+            .as_f64()
+            .ok_or(ColorFilterError::Type { expected: "number" })?,
+        None => 0.5,
+    };
 
-    #[test]
-    fn test_rgb_filter_invalid_length() {
-        let result = rgb(&json!("FFF"), &HashMap::new());
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("Invalid hex code length")
-        );
+    Ok(Value::Bool(parse_hex_color(value)?.luminance() < threshold))
+}
 
-        let result = rgb(&json!("FF5733AA"), &HashMap::new());
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("Invalid hex code length")
-        );
-    }
+/// Linear RGB to LMS cone response (Viénot, Brettel & Mollon, 1999).
+const RGB_TO_LMS: [[f64; 3]; 3] = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.0299566, 0.184309, 1.46709],
+];
 
-    #[test]
-    fn test_rgb_filter_invalid_hex_digits() {
-        let result = rgb(&json!("GGGGGG"), &HashMap::new());
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("Invalid hex value")
-        );
+/// Inverse of [`RGB_TO_LMS`].
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+    [0.0809444479, -0.130504409, 0.116721066],
+    [-0.0102485335, 0.0540193266, -0.113614708],
+    [-0.000365296938, -0.00412161469, 0.693511405],
+];
 
-        let result = rgb(&json!("12345Z"), &HashMap::new());
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
+/// Approximates how a color looks with a color vision deficiency.
+///
+/// The color is converted to LMS cone space, the response of the missing
+/// cone type is rebuilt from the other two, and the result is converted back.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `type`: `prot` (protanopia), `deut` (deuteranopia) or `trit`
+///     (tritanopia)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | simulate_cvd(type="prot") }}   -> "5e5e0d"
+/// {{ base08 | simulate_cvd(type="deut") | hex_hash }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color, or `type` is
+/// missing or not one of the supported deficiencies.
+pub fn simulate_cvd(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let kind = required_arg(args, "type")?
+        .as_str()
+        .ok_or(ColorFilterError::Type { expected: "string" })?;
+
+    let simulation: [[f64; 3]; 3] = match kind {
+        "prot" => [[0.0, 2.02344, -2.52581], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        "deut" => [[1.0, 0.0, 0.0], [0.494207, 0.0, 1.24827], [0.0, 0.0, 1.0]],
+        "trit" => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [-0.395913, 0.801109, 0.0]],
+        _ => {
+            return Err(ColorFilterError::UnknownCvdType {
+                value: kind.to_string(),
+            }
+            .into());
+        }
+    };
+
+    let linear = parse_hex_color(value)?.to_linear();
+
+    let transform =
+        |m: &[[f64; 3]; 3], v: [f64; 3]| m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2]);
+    let simulated = transform(
+        &LMS_TO_RGB,
+        transform(&simulation, transform(&RGB_TO_LMS, linear)),
+    );
+
+    Ok(Value::String(Color::from_linear(simulated).to_hex()))
+}
+
+/// HSL lightness change per `contrast_adjust` step; 100 steps cover the
+/// whole range.
+const CONTRAST_STEP: f64 = 0.01;
+const CONTRAST_MAX_STEPS: usize = 100;
+
+/// Lightens or darkens a foreground color until its contrast ratio against
+/// `bg` reaches `ratio`.
+///
+/// The color moves away from the background's luminance in HSL lightness
+/// steps, keeping its hue and saturation. If that direction can't reach the
+/// target but the other can, the other direction is used. When the target is
+/// unreachable, the color with the highest contrast found is returned.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Arguments:
+///   - `bg` (required): Background hex color
+///   - `ratio` (optional): Target contrast ratio, defaults to 4.5 (WCAG AA)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ base03 | contrast_adjust(bg=base00) | hex_hash }}
+/// {{ "777777" | contrast_adjust(bg="ffffff", ratio=7) }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if either color is not a valid hex color, `bg` is
+/// missing, or `ratio` is not a number.
+pub fn contrast_adjust(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let bg = parse_hex_color(required_arg(args, "bg")?)?.luminance();
+    let target = match args.get("ratio") {
+        Some(ratio) => ratio
+            .as_f64()
+            .ok_or(ColorFilterError::Type { expected: "number" })?,
+        None => 4.5,
+    };
+
+    let fg = parse_hex_color(value)?;
+    let contrast = |color: Color| color::contrast_ratio(color.luminance(), bg);
+    if contrast(fg) >= target {
+        return Ok(Value::String(fg.to_hex()));
+    }
+
+    let lighter_reaches = color::contrast_ratio(1.0, bg) >= target;
+    let darker_reaches = color::contrast_ratio(0.0, bg) >= target;
+    let lighten = if fg.luminance() >= bg {
+        lighter_reaches || !darker_reaches
+    } else {
+        lighter_reaches && !darker_reaches
+    };
+    let step = if lighten {
+        CONTRAST_STEP
+    } else {
+        -CONTRAST_STEP
+    };
+
+    let (h, s, mut l) = fg.to_hsl();
+    let mut best = fg;
+    for _ in 0..CONTRAST_MAX_STEPS {
+        l = (l + step).clamp(0.0, 1.0);
+        let candidate = Color::from_hsl(h, s, l);
+        if contrast(candidate) > contrast(best) {
+            best = candidate;
+        }
+        if contrast(candidate) >= target || l == 0.0 || l == 1.0 {
+            break;
+        }
+    }
+
+    Ok(Value::String(best.to_hex()))
+}
+
+/// Snaps a color to the 216-color web-safe palette by rounding each channel
+/// to the nearest multiple of 51 (`00`, `33`, `66`, `99`, `cc`, `ff`).
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "fd0307" | web_safe }}              -> "ff0000"
+/// {{ base0D | web_safe | hex_hash }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn web_safe(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let snap = |channel: u8| ((f64::from(channel) / 51.0).round() * 51.0) as u8;
+    let Color { r, g, b } = parse_hex_color(value)?;
+
+    Ok(Value::String(
+        Color::new(snap(r), snap(g), snap(b)).to_hex(),
+    ))
+}
+
+/// Composites a color with opacity `a` over an opaque background, giving
+/// the solid color a semi-transparent overlay would show.
+///
+/// Each channel is `a * fg + (1 - a) * bg`.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `bg`: Background hex color
+///   - `a`: Foreground opacity in [0.0, 1.0]
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ffffff" | blend_over(bg="000000", a=0.5) }}   -> "808080"
+/// {{ base0D | blend_over(bg=base00, a=0.2) | hex_hash }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if either color is not a valid hex color, or an argument
+/// is missing, or `a` is not a number in [0.0, 1.0].
+pub fn blend_over(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let bg = parse_hex_color(required_arg(args, "bg")?)?;
+    let alpha = required_alpha(args)?;
+    let fg = parse_hex_color(value)?;
+
+    Ok(Value::String(bg.mix(fg, alpha).to_hex()))
+}
+
+/// Channel values of the 6×6×6 color cube (indices 16–231).
+const CUBE_LEVELS: [u8; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
+/// Maps a color to the nearest index in the xterm 256-color palette.
+///
+/// Candidates are the closest color in the 16–231 color cube and the
+/// closest shade in the 232–255 grayscale ramp; whichever is nearer in RGB
+/// wins. The 16 system colors are skipped since terminals redefine them.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "000000" | ansi256 }}   -> 16
+/// {{ "ffffff" | ansi256 }}   -> 231
+/// set -g status-style bg=colour{{ base01 | ansi256 }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn ansi256(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let Color { r, g, b } = parse_hex_color(value)?;
+
+    let cube_index = |v: u8| match v {
+        0..48 => 0,
+        48..115 => 1,
+        _ => (usize::from(v) - 35) / 40,
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let average = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let gray_index = if average > 238 {
+        23
+    } else {
+        average.saturating_sub(3) / 10
+    };
+    let gray_level = (8 + 10 * gray_index) as u8;
+
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+
+    let index = if distance((gray_level, gray_level, gray_level)) < distance(cube) {
+        232 + gray_index as usize
+    } else {
+        16 + 36 * ri + 6 * gi + bi
+    };
+
+    Ok(Value::from(index))
+}
+
+/// Shortens a color to 3-digit hex when that loses nothing, i.e. when both
+/// digits of every channel match. Other colors keep all 6 digits.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | short_hex }}             -> "f00"
+/// {{ "ff5733" | short_hex }}             -> "ff5733"
+/// {{ base08 | short_hex | hex_hash }}    -> "#f00"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid 6-digit hex color.
+pub fn short_hex(value: &Value, _args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let Color { r, g, b } = parse_hex_color(value)?;
+    let hex = Color::new(r, g, b).to_hex();
+
+    if [r, g, b]
+        .iter()
+        .all(|&channel| channel >> 4 == channel & 0x0f)
+    {
+        let short: String = hex.chars().step_by(2).collect();
+        return Ok(Value::String(short));
+    }
+
+    Ok(Value::String(hex))
+}
+
+/// Mixes a color toward white in RGB, giving a tint. Unlike adjusting HSL
+/// lightness, this also washes out the saturation, which suits UI ramps.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `amount`: How far to move toward white, in [0.0, 1.0]
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | tint(amount=0.5) }}      -> "ff8080"
+/// {{ base0D | tint(amount=0.2) | hex_hash }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color, or `amount` is
+/// missing or not a number in [0.0, 1.0].
+pub fn tint(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let amount = required_amount(args)?;
+    let color = parse_hex_color(value)?;
+
+    Ok(Value::String(color.mix(Color::WHITE, amount).to_hex()))
+}
+
+/// Mixes a color toward black in RGB, giving a shade. The counterpart of
+/// [`tint`].
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `amount`: How far to move toward black, in [0.0, 1.0]
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff0000" | shade(amount=0.5) }}     -> "800000"
+/// {{ base00 | shade(amount=0.3) | hex_hash }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the input is not a valid hex color, or `amount` is
+/// missing or not a number in [0.0, 1.0].
+pub fn shade(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let amount = required_amount(args)?;
+    let color = parse_hex_color(value)?;
+
+    Ok(Value::String(color.mix(Color::BLACK, amount).to_hex()))
+}
+
+/// Returns the color unchanged when it is a valid hex color, otherwise the
+/// fallback. Unlike Tera's `default`, which only covers undefined values,
+/// empty strings and invalid hex also count as missing.
+///
+/// # Arguments
+///
+/// * `value` - A hex color string (with or without '#' prefix)
+/// * `args` - Required arguments:
+///   - `value`: The value used when the input is missing or invalid
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ "ff5733" | fallback(value="000000") }}   -> "ff5733"
+/// {{ "" | fallback(value=base05) }}           -> base05
+/// {{ "nope" | fallback(value="000000") }}     -> "000000"
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `value` is missing.
+pub fn fallback(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let fallback = required_arg(args, "value")?;
+
+    if parse_hex_color(value).is_ok() {
+        Ok(value.clone())
+    } else {
+        Ok(fallback.clone())
+    }
+}
+
+/// Computes the WCAG contrast ratio between two colors.
+///
+/// This is registered as a global Tera function rather than a filter.
+///
+/// # Arguments
+///
+/// * `args` - Required arguments:
+///   - `fg`: Foreground hex color
+///   - `bg`: Background hex color
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ contrast_ratio(fg="000000", bg="ffffff") }}    -> 21.0
+/// {% if contrast_ratio(fg=base05, bg=base00) < 4.5 %}low contrast{% endif %}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if either argument is missing or not a valid hex color.
+pub fn contrast_ratio(args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let fg = required_arg(args, "fg")?;
+    let bg = required_arg(args, "bg")?;
+
+    let ratio = parse_hex_color(fg)?.contrast(parse_hex_color(bg)?);
+
+    Ok(Value::from(ratio))
+}
+
+/// Generates evenly spaced colors from one color to another, including both
+/// endpoints.
+///
+/// This is registered as a global Tera function rather than a filter.
+///
+/// # Arguments
+///
+/// * `args` - Required arguments:
+///   - `from`: Starting hex color
+///   - `to`: Ending hex color
+///   - `steps`: Number of colors to return, at least 2
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ gradient(from="000000", to="ffffff", steps=3) }}   -> ["000000", "808080", "ffffff"]
+/// {% for c in gradient(from=base08, to=base0B, steps=5) %}{{ c }} {% endfor %}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if an argument is missing, either color is not a valid hex
+/// color, or `steps` is not an integer of at least 2.
+pub fn gradient(args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let from = parse_hex_color(required_arg(args, "from")?)?;
+    let to = parse_hex_color(required_arg(args, "to")?)?;
+    let steps = required_arg(args, "steps")?
+        .as_u64()
+        .ok_or(ColorFilterError::Type {
+            expected: "integer",
+        })?;
+
+    if steps < 2 {
+        return Err(ColorFilterError::StepsRange { value: steps }.into());
+    }
+
+    let last = (steps - 1) as f64;
+    let colors = (0..steps)
+        .map(|step| Value::String(from.mix(to, step as f64 / last).to_hex()))
+        .collect();
+
+    Ok(Value::Array(colors))
+}
+
+/// Reads the required `a` argument, checking it's a number in [0.0, 1.0].
+fn required_alpha(args: &HashMap<String, Value>) -> TeraResult<f64> {
+    let alpha = required_arg(args, "a")?
+        .as_f64()
+        .ok_or(ColorFilterError::Type { expected: "number" })?;
+
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(ColorFilterError::AlphaRange { value: alpha }.into());
+    }
+
+    Ok(alpha)
+}
+
+fn required_amount(args: &HashMap<String, Value>) -> TeraResult<f64> {
+    let amount = required_arg(args, "amount")?
+        .as_f64()
+        .ok_or(ColorFilterError::Type { expected: "number" })?;
+
+    if !(0.0..=1.0).contains(&amount) {
+        return Err(ColorFilterError::AmountRange { value: amount }.into());
+    }
+
+    Ok(amount)
+}
+
+/// Builds a set of harmonious colors from a seed by rotating its hue.
+///
+/// This is registered as a global Tera function rather than a filter. The
+/// seed always comes first, followed by its rotations:
+///
+/// - `complementary`: 0°, 180°
+/// - `triadic`: 0°, 120°, 240°
+/// - `analogous`: 0°, 30°, 330°
+/// - `tetradic`: 0°, 90°, 180°, 270°
+///
+/// # Arguments
+///
+/// * `args` - Required arguments:
+///   - `seed`: Hex color to derive the scheme from
+///   - `kind`: One of the scheme kinds above
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ scheme(seed="ff0000", kind="triadic") }}   -> ["ff0000", "00ff00", "0000ff"]
+/// {% set accents = scheme(seed=base0D, kind="tetradic") %}{{ accents[2] }}
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if an argument is missing, `seed` is not a valid hex
+/// color, or `kind` is not a supported scheme.
+pub fn scheme(args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let seed = parse_hex_color(required_arg(args, "seed")?)?;
+    let kind = required_arg(args, "kind")?
+        .as_str()
+        .ok_or(ColorFilterError::Type { expected: "string" })?;
+
+    let rotations: &[f64] = match kind {
+        "complementary" => &[0.0, 180.0],
+        "triadic" => &[0.0, 120.0, 240.0],
+        "analogous" => &[0.0, 30.0, 330.0],
+        "tetradic" => &[0.0, 90.0, 180.0, 270.0],
+        _ => {
+            return Err(ColorFilterError::UnknownSchemeKind {
+                value: kind.to_string(),
+            }
+            .into());
+        }
+    };
+
+    let colors = rotations
+        .iter()
+        .map(|&degrees| Value::String(seed.rotate_hue(degrees).to_hex()))
+        .collect();
+
+    Ok(Value::Array(colors))
+}
+
+/// Snaps a color to the closest of the palette's base16 colors, measured
+/// as perceptual distance in CIE L*a*b* (CIE76).
+///
+/// This backs the `nearest` Tera function, which the template engine binds
+/// to the palette being rendered, so templates only pass the color.
+///
+/// # Arguments
+///
+/// * `palette` - The base16 colors to choose from, `base00` through `base0F`
+/// * `args` - Required arguments:
+///   - `color`: Hex color to snap
+///
+/// # Examples
+///
+/// In a Tera template:
+/// ```text
+/// {{ nearest(color="fa4a35") }}   -> "fb4934" (when that is base08)
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `color` is missing or not a valid hex color, or if
+/// the palette is empty.
+pub fn nearest(palette: &[Value], args: &HashMap<String, Value>) -> TeraResult<Value> {
+    let color = parse_hex_color(required_arg(args, "color")?)?;
+
+    let mut best: Option<(&Value, f64)> = None;
+    for candidate in palette {
+        let distance = color.distance(parse_hex_color(candidate)?);
+        if best.is_none_or(|(_, best_distance)| distance < best_distance) {
+            best = Some((candidate, distance));
+        }
+    }
+
+    best.map(|(candidate, _)| candidate.clone())
+        .ok_or_else(|| TeraError::msg("nearest() requires a palette"))
+}
+
+fn required_arg<'a>(args: &'a HashMap<String, Value>, name: &'static str) -> TeraResult<&'a Value> {
+    args.get(name)
+        .ok_or_else(|| ColorFilterError::MissingArgument { name }.into())
+}
+
+/// Parses a 6-digit hex color value (with or without '#') with
+/// [`Color::from_hex`], with errors that say which part of it is wrong.
+fn parse_hex_color(value: &Value) -> Result<Color, TeraError> {
+    let hex_str = value
+        .as_str()
+        .ok_or(ColorFilterError::Type { expected: "string" })?;
+
+    Color::from_hex(hex_str).map_err(|_| hex_error(hex_str).into())
+}
+
+/// Explains why `Color::from_hex` rejected `hex_str`: either its length or
+/// the first component holding a non-hex digit.
+fn hex_error(hex_str: &str) -> ColorFilterError {
+    let hex_code = hex_str.strip_prefix('#').unwrap_or(hex_str);
+    // Work on chars rather than bytes so non-ASCII input is reported as an
+    // invalid digit instead of slicing through a multi-byte character.
+    let chars: Vec<char> = hex_code.chars().collect();
+    if chars.len() != 6 {
+        return ColorFilterError::HexLength {
+            actual: chars.len(),
+        };
+    }
+
+    let (component, digits) = ["Red", "Green", "Blue"]
+        .into_iter()
+        .zip(chars.chunks(2))
+        .find(|(_, digits)| !digits.iter().all(char::is_ascii_hexdigit))
+        .unwrap_or(("Red", &chars[0..2]));
+    ColorFilterError::HexDigit {
+        component,
+        value: digits.iter().collect(),
+    }
+}
+
+#[inline]
+fn format_rgb_output(r: u8, g: u8, b: u8, alpha: f64) -> String {
+    const EPSILON: f64 = 1e-10;
+
+    if (alpha - 1.0).abs() < EPSILON {
+        format!("rgb({}, {}, {})", r, g, b)
+    } else {
+        format!("rgba({}, {}, {}, {:.2})", r, g, b, alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hex_hash_filter_success() {
+        let result = hex_hash(&Value::String("FF5733".to_string()), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("#FF5733"));
+
+        let result = hex_hash(&json!("000000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("#000000"));
+    }
+
+    #[test]
+    fn test_hex_hash_filter_upper() {
+        let upper = HashMap::from([("upper".to_string(), json!(true))]);
+        let result = hex_hash(&json!("ff5733"), &upper);
+        assert_eq!(result.unwrap(), json!("#FF5733"));
+
+        let keep = HashMap::from([("upper".to_string(), json!(false))]);
+        let result = hex_hash(&json!("ff5733"), &keep);
+        assert_eq!(result.unwrap(), json!("#ff5733"));
+
+        let result = hex_hash(&json!("ff5733"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("#ff5733"));
+    }
+
+    #[test]
+    fn test_hex_hash_filter_upper_must_be_boolean() {
+        let args = HashMap::from([("upper".to_string(), json!("yes"))]);
+        let result = hex_hash(&json!("ff5733"), &args);
+        assert!(result.unwrap_err().to_string().contains("expected boolean"));
+    }
+
+    #[test]
+    fn test_hex_hash_filter_invalid_type() {
+        let result = hex_hash(&Value::Number(123.into()), &HashMap::new());
+        assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_rgb_filter_basic_colors() {
+        // Without hash prefix
+        let result = rgb(&json!("FF5733"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("rgb(255, 87, 51)"));
+
+        // With hash prefix
+        let result = rgb(&json!("#2E8B57"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("rgb(46, 139, 87)"));
+
+        // Black and white
+        let result = rgb(&json!("000000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("rgb(0, 0, 0)"));
+
+        let result = rgb(&json!("FFFFFF"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("rgb(255, 255, 255)"));
+    }
+
+    #[test]
+    fn test_rgb_filter_with_alpha() {
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.5));
+        let result = rgb(&json!("4169E1"), &args);
+        assert_eq!(result.unwrap(), json!("rgba(65, 105, 225, 0.50)"));
+
+        // Alpha = 0.0
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.0));
+        let result = rgb(&json!("FFFFFF"), &args);
+        assert_eq!(result.unwrap(), json!("rgba(255, 255, 255, 0.00)"));
+
+        // Alpha = 1.0 should omit alpha
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(1.0));
+        let result = rgb(&json!("FFFFFF"), &args);
+        assert_eq!(result.unwrap(), json!("rgb(255, 255, 255)"));
+    }
+
+    #[test]
+    fn test_rgb_filter_invalid_type() {
+        let result = rgb(&json!(12345), &HashMap::new());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_rgb_filter_invalid_length() {
+        let result = rgb(&json!("FFF"), &HashMap::new());
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid hex code length")
+        );
+
+        let result = rgb(&json!("FF5733AA"), &HashMap::new());
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid hex code length")
+        );
+    }
+
+    #[test]
+    fn test_rgb_filter_invalid_hex_digits() {
+        let result = rgb(&json!("GGGGGG"), &HashMap::new());
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid hex value")
+        );
+
+        let result = rgb(&json!("12345Z"), &HashMap::new());
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid hex value")
+        );
+    }
+
+    #[test]
+    fn test_parse_hex_color_names_bad_component() {
+        let err = parse_hex_color(&json!("#12345Z")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid hex value for Blue component: '5Z'"
+        );
+
+        let err = parse_hex_color(&json!("12é456")).unwrap_err();
+        assert!(err.to_string().contains("Green component"));
+    }
+
+    #[test]
+    fn test_rgb_filter_invalid_alpha_range() {
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(1.5));
+        let result = rgb(&json!("FFFFFF"), &args);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("must be between 0.0 and 1.0")
+        );
+
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(-0.5));
+        let result = rgb(&json!("FFFFFF"), &args);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("must be between 0.0 and 1.0")
+        );
+    }
+
+    #[test]
+    fn test_contrast_color_dark_background_picks_white() {
+        let result = contrast_color(&json!("1e1e2e"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("ffffff"));
+
+        let result = contrast_color(&json!("#000000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("ffffff"));
+    }
+
+    #[test]
+    fn test_contrast_color_light_background_picks_black() {
+        let result = contrast_color(&json!("f5f5f5"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("000000"));
+
+        let result = contrast_color(&json!("ffff00"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("000000"));
+    }
+
+    #[test]
+    fn test_contrast_color_custom_candidates() {
+        let mut args = HashMap::new();
+        args.insert("light".to_string(), json!("eceff4"));
+        args.insert("dark".to_string(), json!("2e3440"));
+
+        let result = contrast_color(&json!("101010"), &args);
+        assert_eq!(result.unwrap(), json!("eceff4"));
+
+        let result = contrast_color(&json!("fafafa"), &args);
+        assert_eq!(result.unwrap(), json!("2e3440"));
+    }
+
+    #[test]
+    fn test_contrast_color_invalid_input() {
+        let result = contrast_color(&json!("xyz"), &HashMap::new());
+        assert!(result.is_err());
+
+        let mut args = HashMap::new();
+        args.insert("light".to_string(), json!("nothex"));
+        let result = contrast_color(&json!("000000"), &args);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_contrast_ratio_black_on_white() {
+        let mut args = HashMap::new();
+        args.insert("fg".to_string(), json!("000000"));
+        args.insert("bg".to_string(), json!("#ffffff"));
+
+        let ratio = contrast_ratio(&args).unwrap().as_f64().unwrap();
+        assert!((ratio - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contrast_ratio_identical_colors() {
+        let mut args = HashMap::new();
+        args.insert("fg".to_string(), json!("83a598"));
+        args.insert("bg".to_string(), json!("83a598"));
+
+        let ratio = contrast_ratio(&args).unwrap().as_f64().unwrap();
+        assert!((ratio - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contrast_ratio_is_symmetric() {
+        let mut args = HashMap::new();
+        args.insert("fg".to_string(), json!("282828"));
+        args.insert("bg".to_string(), json!("d5c4a1"));
+        let forward = contrast_ratio(&args).unwrap().as_f64().unwrap();
+
+        args.insert("fg".to_string(), json!("d5c4a1"));
+        args.insert("bg".to_string(), json!("282828"));
+        let backward = contrast_ratio(&args).unwrap().as_f64().unwrap();
+
+        assert!((forward - backward).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_contrast_ratio_missing_argument() {
+        let mut args = HashMap::new();
+        args.insert("fg".to_string(), json!("000000"));
+
+        let result = contrast_ratio(&args);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing required argument 'bg'")
+        );
+    }
+
+    #[test]
+    fn test_rotate_hue_full_turn_is_noop() {
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!(360));
+
+        for color in ["ff5733", "83a598", "282828", "d3869b"] {
+            let result = rotate_hue(&json!(color), &args);
+            assert_eq!(result.unwrap(), json!(color));
+        }
+    }
+
+    #[test]
+    fn test_rotate_hue_red_to_green_and_blue() {
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!(120));
+        let result = rotate_hue(&json!("ff0000"), &args);
+        assert_eq!(result.unwrap(), json!("00ff00"));
+
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!(-120));
+        let result = rotate_hue(&json!("#ff0000"), &args);
+        assert_eq!(result.unwrap(), json!("0000ff"));
+    }
+
+    #[test]
+    fn test_rotate_hue_complement() {
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!(180));
+        let result = rotate_hue(&json!("ff0000"), &args);
+        assert_eq!(result.unwrap(), json!("00ffff"));
+    }
+
+    #[test]
+    fn test_rotate_hue_grey_is_unchanged() {
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!(90));
+        let result = rotate_hue(&json!("808080"), &args);
+        assert_eq!(result.unwrap(), json!("808080"));
+    }
+
+    #[test]
+    fn test_rotate_hue_invalid_arguments() {
+        let result = rotate_hue(&json!("ff0000"), &HashMap::new());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing required argument 'degrees'")
+        );
+
+        let mut args = HashMap::new();
+        args.insert("degrees".to_string(), json!("ninety"));
+        let result = rotate_hue(&json!("ff0000"), &args);
+        assert!(result.unwrap_err().to_string().contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_alpha_bounds() {
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(1.0));
+        let result = alpha(&json!("1E1E2E"), &args);
+        assert_eq!(result.unwrap(), json!("1e1e2eff"));
+
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.0));
+        let result = alpha(&json!("#1e1e2e"), &args);
+        assert_eq!(result.unwrap(), json!("1e1e2e00"));
+    }
+
+    #[test]
+    fn test_alpha_rounds_to_nearest_byte() {
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.5));
+        let result = alpha(&json!("ffffff"), &args);
+        assert_eq!(result.unwrap(), json!("ffffff80"));
+    }
+
+    #[test]
+    fn test_alpha_invalid_input() {
+        let result = alpha(&json!("ffffff"), &HashMap::new());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Missing required argument 'a'")
+        );
+
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(1.5));
+        let result = alpha(&json!("ffffff"), &args);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("between 0.0 and 1.0")
+        );
+
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.5));
+        let result = alpha(&json!("fff"), &args);
+        assert!(result.unwrap_err().to_string().contains("hex code length"));
+    }
+
+    #[test]
+    fn test_strip_hash_filter() {
+        let result = strip_hash(&json!("#ff0000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("ff0000"));
+
+        let result = strip_hash(&json!("ff0000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("ff0000"));
+
+        // Only a single leading '#' is removed
+        let result = strip_hash(&json!("##ff0000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("#ff0000"));
+    }
+
+    #[test]
+    fn test_strip_hash_filter_invalid_type() {
+        let result = strip_hash(&json!(123), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_bgr_filter() {
+        let result = bgr(&json!("ff0000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("0000ff"));
+
+        let result = bgr(&json!("#123456"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("563412"));
+
+        // Symmetric colors are unchanged
+        let result = bgr(&json!("a0b0a0"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!("a0b0a0"));
+    }
+
+    #[test]
+    fn test_bgr_filter_invalid_input() {
+        let result = bgr(&json!("ff00"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("hex code length"));
+
+        let result = bgr(&json!("gg0000"), &HashMap::new());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
                 .contains("Invalid hex value")
         );
     }
 
     #[test]
-    fn test_rgb_filter_invalid_alpha_range() {
+    fn test_int_filter() {
+        let result = int(&json!("ff0000"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!(16711680));
+
+        let result = int(&json!("#000001"), &HashMap::new());
+        assert_eq!(result.unwrap(), json!(1));
+    }
+
+    #[test]
+    fn test_int_filter_with_prefix() {
         let mut args = HashMap::new();
-        args.insert("a".to_string(), json!(1.5));
-        let result = rgb(&json!("FFFFFF"), &args);
+        args.insert("prefix".to_string(), json!("0x"));
+
+        let result = int(&json!("FF0000"), &args);
+        assert_eq!(result.unwrap(), json!("0xff0000"));
+
+        let result = int(&json!("0000ff"), &args);
+        assert_eq!(result.unwrap(), json!("0x0000ff"));
+    }
+
+    #[test]
+    fn test_int_filter_invalid_input() {
+        let result = int(&json!("red"), &HashMap::new());
         assert!(result.is_err());
+
+        let mut args = HashMap::new();
+        args.insert("prefix".to_string(), json!(1));
+        let result = int(&json!("ff0000"), &args);
+        assert!(result.unwrap_err().to_string().contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_rgb_components_filter() {
+        let result = rgb_components(&json!("#ff8000"), &HashMap::new()).unwrap();
+
+        let components = result.as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(result, json!([255, 128, 0]));
+    }
+
+    #[test]
+    fn test_rgb_components_filter_invalid_input() {
+        let result = rgb_components(&json!(255), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("Invalid type"));
+    }
+
+    #[test]
+    fn test_luminance_black_and_white() {
+        let args = HashMap::new();
+
+        let black = luminance(&json!("000000"), &args)
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        let white = luminance(&json!("#FFFFFF"), &args)
+            .unwrap()
+            .as_f64()
+            .unwrap();
+
+        assert!(black.abs() < 1e-9);
+        assert!((white - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_luminance_orders_grays() {
+        let args = HashMap::new();
+
+        let dark = luminance(&json!("282828"), &args)
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        let light = luminance(&json!("d5c4a1"), &args)
+            .unwrap()
+            .as_f64()
+            .unwrap();
+
+        assert!(dark < 0.5 && light > 0.5);
+    }
+
+    #[test]
+    fn test_luminance_invalid_color() {
+        assert!(luminance(&json!("zzz"), &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_is_dark_dark_and_light_colors() {
+        let args = HashMap::new();
+
+        assert_eq!(is_dark(&json!("282828"), &args).unwrap(), json!(true));
+        assert_eq!(is_dark(&json!("#fbf1c7"), &args).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_is_dark_custom_threshold() {
+        let mut args = HashMap::new();
+        args.insert("threshold".to_string(), json!(0.1));
+
+        // 777777 has a luminance of about 0.18.
+        assert_eq!(is_dark(&json!("777777"), &args).unwrap(), json!(false));
+        assert_eq!(
+            is_dark(&json!("777777"), &HashMap::new()).unwrap(),
+            json!(true)
+        );
+    }
+
+    #[test]
+    fn test_is_dark_invalid_threshold() {
+        let mut args = HashMap::new();
+        args.insert("threshold".to_string(), json!("high"));
+
+        let err = is_dark(&json!("000000"), &args).unwrap_err();
+        assert!(err.to_string().contains("expected number"));
+    }
+
+    fn gradient_args(from: &str, to: &str, steps: Value) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("from".to_string(), json!(from));
+        args.insert("to".to_string(), json!(to));
+        args.insert("steps".to_string(), steps);
+        args
+    }
+
+    #[test]
+    fn test_gradient_three_steps() {
+        let result = gradient(&gradient_args("000000", "#ffffff", json!(3))).unwrap();
+
+        assert_eq!(result, json!(["000000", "808080", "ffffff"]));
+    }
+
+    #[test]
+    fn test_gradient_two_steps_is_endpoints() {
+        let result = gradient(&gradient_args("fb4934", "b8bb26", json!(2))).unwrap();
+
+        assert_eq!(result, json!(["fb4934", "b8bb26"]));
+    }
+
+    #[test]
+    fn test_gradient_rejects_too_few_steps() {
+        let err = gradient(&gradient_args("000000", "ffffff", json!(1))).unwrap_err();
+        assert_eq!(err.to_string(), "Gradient needs at least 2 steps, got 1");
+
+        let err = gradient(&gradient_args("000000", "ffffff", json!(2.5))).unwrap_err();
+        assert!(err.to_string().contains("expected integer"));
+    }
+
+    #[test]
+    fn test_gradient_rejects_invalid_color() {
+        assert!(gradient(&gradient_args("000000", "fff", json!(3))).is_err());
+        assert!(gradient(&HashMap::new()).is_err());
+    }
+
+    fn cvd_args(kind: &str) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("type".to_string(), json!(kind));
+        args
+    }
+
+    #[test]
+    fn test_simulate_cvd_protanopia_red() {
+        let result = simulate_cvd(&json!("ff0000"), &cvd_args("prot")).unwrap();
+
+        // Red loses most of its brightness and shifts to a dark olive.
+        assert_eq!(result, json!("5e5e0d"));
+    }
+
+    #[test]
+    fn test_simulate_cvd_preserves_grays() {
+        for kind in ["prot", "deut", "trit"] {
+            let result = simulate_cvd(&json!("#808080"), &cvd_args(kind)).unwrap();
+            let Color { r, g, b } = parse_hex_color(&result).unwrap();
+
+            for channel in [r, g, b] {
+                assert!(channel.abs_diff(0x80) <= 1, "{}: {}", kind, result);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulate_cvd_deuteranopia_merges_red_and_green() {
+        let red = simulate_cvd(&json!("ff0000"), &cvd_args("deut")).unwrap();
+        let Color { r, g, .. } = parse_hex_color(&red).unwrap();
+
+        assert!(r.abs_diff(g) < 0x20, "{}", red);
+    }
+
+    #[test]
+    fn test_simulate_cvd_unknown_type() {
+        let err = simulate_cvd(&json!("ff0000"), &cvd_args("mono")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown color vision deficiency type 'mono'")
+        );
+
+        assert!(simulate_cvd(&json!("ff0000"), &HashMap::new()).is_err());
+    }
+
+    fn contrast_adjust_args(bg: &str, ratio: Option<f64>) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("bg".to_string(), json!(bg));
+        if let Some(ratio) = ratio {
+            args.insert("ratio".to_string(), json!(ratio));
+        }
+        args
+    }
+
+    fn contrast_of(fg: &Value, bg: &str) -> f64 {
+        parse_hex_color(fg)
+            .unwrap()
+            .contrast(parse_hex_color(&json!(bg)).unwrap())
+    }
+
+    #[test]
+    fn test_contrast_adjust_meets_default_ratio() {
+        let result =
+            contrast_adjust(&json!("504945"), &contrast_adjust_args("282828", None)).unwrap();
+
+        assert!(contrast_of(&result, "282828") >= 4.5, "{}", result);
+    }
+
+    #[test]
+    fn test_contrast_adjust_darkens_on_light_background() {
+        let result = contrast_adjust(
+            &json!("#aaaaaa"),
+            &contrast_adjust_args("ffffff", Some(7.0)),
+        )
+        .unwrap();
+        let Color { r, .. } = parse_hex_color(&result).unwrap();
+
+        assert!(contrast_of(&result, "ffffff") >= 7.0, "{}", result);
+        assert!(r < 0xaa);
+    }
+
+    #[test]
+    fn test_contrast_adjust_keeps_sufficient_contrast() {
+        let result =
+            contrast_adjust(&json!("#FFFFFF"), &contrast_adjust_args("000000", None)).unwrap();
+
+        assert_eq!(result, json!("ffffff"));
+    }
+
+    #[test]
+    fn test_contrast_adjust_unreachable_returns_best_effort() {
+        let result = contrast_adjust(
+            &json!("777777"),
+            &contrast_adjust_args("808080", Some(21.0)),
+        )
+        .unwrap();
+
         assert!(
+            result == json!("000000") || result == json!("ffffff"),
+            "{}",
             result
-                .unwrap_err()
-                .to_string()
-                .contains("must be between 0.0 and 1.0")
         );
+    }
+
+    #[test]
+    fn test_contrast_adjust_invalid_arguments() {
+        assert!(contrast_adjust(&json!("777777"), &HashMap::new()).is_err());
+
+        let mut args = contrast_adjust_args("000000", None);
+        args.insert("ratio".to_string(), json!("high"));
+        assert!(contrast_adjust(&json!("777777"), &args).is_err());
+    }
+
+    #[test]
+    fn test_web_safe_keeps_web_safe_colors() {
+        let result = web_safe(&json!("ff0000"), &HashMap::new()).unwrap();
+        assert_eq!(result, json!("ff0000"));
+
+        let result = web_safe(&json!("#3366CC"), &HashMap::new()).unwrap();
+        assert_eq!(result, json!("3366cc"));
+    }
+
+    #[test]
+    fn test_web_safe_snaps_near_misses() {
+        let result = web_safe(&json!("fd0307"), &HashMap::new()).unwrap();
+        assert_eq!(result, json!("ff0000"));
+
+        // 0x19 = 25 rounds down to 0, 0x1a = 26 rounds up to 51
+        let result = web_safe(&json!("191a80"), &HashMap::new()).unwrap();
+        assert_eq!(result, json!("003399"));
+    }
+
+    #[test]
+    fn test_web_safe_invalid_input() {
+        assert!(web_safe(&json!("nothex"), &HashMap::new()).is_err());
+    }
+
+    fn blend_args(bg: &str, a: Value) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("bg".to_string(), json!(bg));
+        args.insert("a".to_string(), a);
+        args
+    }
+
+    #[test]
+    fn test_blend_over_opaque_returns_foreground() {
+        let result = blend_over(&json!("#FB4934"), &blend_args("282828", json!(1.0))).unwrap();
+        assert_eq!(result, json!("fb4934"));
+    }
+
+    #[test]
+    fn test_blend_over_transparent_returns_background() {
+        let result = blend_over(&json!("fb4934"), &blend_args("#282828", json!(0.0))).unwrap();
+        assert_eq!(result, json!("282828"));
+    }
+
+    #[test]
+    fn test_blend_over_half() {
+        let result = blend_over(&json!("ffffff"), &blend_args("000000", json!(0.5))).unwrap();
+        assert_eq!(result, json!("808080"));
+    }
+
+    #[test]
+    fn test_blend_over_invalid_arguments() {
+        assert!(blend_over(&json!("ffffff"), &blend_args("000000", json!(1.5))).is_err());
+        assert!(blend_over(&json!("ffffff"), &blend_args("000000", json!("half"))).is_err());
+        assert!(blend_over(&json!("ffffff"), &blend_args("nothex", json!(0.5))).is_err());
+
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), json!(0.5));
+        assert!(blend_over(&json!("ffffff"), &args).is_err());
+    }
+
+    #[test]
+    fn test_ansi256_cube_corners() {
+        assert_eq!(
+            ansi256(&json!("000000"), &HashMap::new()).unwrap(),
+            json!(16)
+        );
+        assert_eq!(
+            ansi256(&json!("#FFFFFF"), &HashMap::new()).unwrap(),
+            json!(231)
+        );
+        assert_eq!(
+            ansi256(&json!("ff0000"), &HashMap::new()).unwrap(),
+            json!(196)
+        );
+        assert_eq!(
+            ansi256(&json!("5f87af"), &HashMap::new()).unwrap(),
+            json!(67)
+        );
+    }
+
+    #[test]
+    fn test_ansi256_prefers_grayscale_ramp() {
+        assert_eq!(
+            ansi256(&json!("808080"), &HashMap::new()).unwrap(),
+            json!(244)
+        );
+        assert_eq!(
+            ansi256(&json!("282828"), &HashMap::new()).unwrap(),
+            json!(235)
+        );
+    }
+
+    #[test]
+    fn test_ansi256_nearest_cube_color() {
+        // fb4934 -> cube (ff, 5f, 5f)
+        assert_eq!(
+            ansi256(&json!("fb4934"), &HashMap::new()).unwrap(),
+            json!(203)
+        );
+    }
 
+    #[test]
+    fn test_ansi256_invalid_input() {
+        assert!(ansi256(&json!("nothex"), &HashMap::new()).is_err());
+    }
+
+    fn scheme_args(seed: &str, kind: &str) -> HashMap<String, Value> {
         let mut args = HashMap::new();
-        args.insert("a".to_string(), json!(-0.5));
-        let result = rgb(&json!("FFFFFF"), &args);
-        assert!(result.is_err());
+        args.insert("seed".to_string(), json!(seed));
+        args.insert("kind".to_string(), json!(kind));
+        args
+    }
+
+    fn hues(colors: &Value) -> Vec<f64> {
+        colors
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|color| parse_hex_color(color).unwrap().to_hsl().0.round())
+            .collect()
+    }
+
+    fn nearest_args(color: &str) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("color".to_string(), json!(color));
+        args
+    }
+
+    fn gruvbox_base16() -> Vec<Value> {
+        [
+            "282828", "3c3836", "504945", "665c54", "bdae93", "d5c4a1", "ebdbb2", "fbf1c7",
+            "fb4934", "fe8019", "fabd2f", "b8bb26", "8ec07c", "83a598", "d3869b", "d65d0e",
+        ]
+        .map(Value::from)
+        .to_vec()
+    }
+
+    #[test]
+    fn test_nearest_snaps_to_closest_base_color() {
+        let palette = gruvbox_base16();
+
+        assert_eq!(
+            nearest(&palette, &nearest_args("fa4a35")).unwrap(),
+            json!("fb4934")
+        );
+        assert_eq!(
+            nearest(&palette, &nearest_args("#FB4934")).unwrap(),
+            json!("fb4934")
+        );
+        assert_eq!(
+            nearest(&palette, &nearest_args("000000")).unwrap(),
+            json!("282828")
+        );
+    }
+
+    #[test]
+    fn test_nearest_invalid_arguments() {
+        let palette = gruvbox_base16();
+
+        assert!(nearest(&palette, &HashMap::new()).is_err());
+        assert!(nearest(&palette, &nearest_args("zzzzzz")).is_err());
+        assert!(nearest(&[], &nearest_args("ff0000")).is_err());
+    }
+
+    #[test]
+    fn test_scheme_triadic() {
+        let result = scheme(&scheme_args("ff0000", "triadic")).unwrap();
+
+        assert_eq!(result, json!(["ff0000", "00ff00", "0000ff"]));
+        assert_eq!(hues(&result), vec![0.0, 120.0, 240.0]);
+    }
+
+    #[test]
+    fn test_scheme_complementary() {
+        let result = scheme(&scheme_args("#83A598", "complementary")).unwrap();
+        let hues = hues(&result);
+
+        assert_eq!(hues.len(), 2);
+        assert!(((hues[1] - hues[0]).rem_euclid(360.0) - 180.0).abs() <= 1.0);
+        assert_eq!(result[0], json!("83a598"));
+    }
+
+    #[test]
+    fn test_scheme_analogous_and_tetradic_lengths() {
+        let analogous = scheme(&scheme_args("ff0000", "analogous")).unwrap();
+        let tetradic = scheme(&scheme_args("ff0000", "tetradic")).unwrap();
+
+        assert_eq!(hues(&analogous), vec![0.0, 30.0, 330.0]);
+        assert_eq!(hues(&tetradic), vec![0.0, 90.0, 180.0, 270.0]);
+    }
+
+    #[test]
+    fn test_scheme_invalid_arguments() {
+        let err = scheme(&scheme_args("ff0000", "square")).unwrap_err();
+        assert!(err.to_string().contains("Unknown scheme kind 'square'"));
+
+        assert!(scheme(&scheme_args("nothex", "triadic")).is_err());
+
+        let mut args = HashMap::new();
+        args.insert("seed".to_string(), json!("ff0000"));
+        assert!(scheme(&args).is_err());
+    }
+
+    #[test]
+    fn test_short_hex_collapses_when_lossless() {
+        assert_eq!(
+            short_hex(&json!("ff0000"), &HashMap::new()).unwrap(),
+            json!("f00")
+        );
+        assert_eq!(
+            short_hex(&json!("#FFFFFF"), &HashMap::new()).unwrap(),
+            json!("fff")
+        );
+        assert_eq!(
+            short_hex(&json!("aabbcc"), &HashMap::new()).unwrap(),
+            json!("abc")
+        );
+    }
+
+    #[test]
+    fn test_short_hex_keeps_six_digits_otherwise() {
+        assert_eq!(
+            short_hex(&json!("ff5733"), &HashMap::new()).unwrap(),
+            json!("ff5733")
+        );
+        assert_eq!(
+            short_hex(&json!("#FF0001"), &HashMap::new()).unwrap(),
+            json!("ff0001")
+        );
+        assert!(short_hex(&json!("f00"), &HashMap::new()).is_err());
+    }
+
+    fn amount_args(amount: f64) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("amount".to_string(), json!(amount));
+        args
+    }
+
+    #[test]
+    fn test_tint_and_shade_endpoints() {
+        assert_eq!(
+            tint(&json!("#83A598"), &amount_args(0.0)).unwrap(),
+            json!("83a598")
+        );
+        assert_eq!(
+            tint(&json!("83a598"), &amount_args(1.0)).unwrap(),
+            json!("ffffff")
+        );
+        assert_eq!(
+            shade(&json!("83a598"), &amount_args(0.0)).unwrap(),
+            json!("83a598")
+        );
+        assert_eq!(
+            shade(&json!("83a598"), &amount_args(1.0)).unwrap(),
+            json!("000000")
+        );
+    }
+
+    #[test]
+    fn test_tint_and_shade_halfway() {
+        assert_eq!(
+            tint(&json!("ff0000"), &amount_args(0.5)).unwrap(),
+            json!("ff8080")
+        );
+        assert_eq!(
+            shade(&json!("ff0000"), &amount_args(0.5)).unwrap(),
+            json!("800000")
+        );
+    }
+
+    #[test]
+    fn test_tint_and_shade_invalid_amount() {
+        let err = tint(&json!("ff0000"), &amount_args(1.5)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Amount 1.5 must be between 0.0 and 1.0")
+        );
+
+        assert!(shade(&json!("ff0000"), &amount_args(-0.1)).is_err());
+        assert!(shade(&json!("ff0000"), &HashMap::new()).is_err());
+    }
+
+    fn fallback_args(value: &str) -> HashMap<String, Value> {
+        HashMap::from([("value".to_string(), json!(value))])
+    }
+
+    #[test]
+    fn test_fallback_passes_valid_color_through() {
+        let result = fallback(&json!("ff5733"), &fallback_args("000000")).unwrap();
+        assert_eq!(result, json!("ff5733"));
+
+        let result = fallback(&json!("#FF5733"), &fallback_args("000000")).unwrap();
+        assert_eq!(result, json!("#FF5733"));
+    }
+
+    #[test]
+    fn test_fallback_replaces_missing_color() {
+        for missing in [
+            json!(""),
+            json!("nope"),
+            json!("fff"),
+            json!("€abc"),
+            json!("€€€€€€"),
+            Value::Null,
+        ] {
+            let result = fallback(&missing, &fallback_args("282828")).unwrap();
+            assert_eq!(result, json!("282828"), "{:?}", missing);
+        }
+    }
+
+    #[test]
+    fn test_fallback_requires_value() {
+        let result = fallback(&json!(""), &HashMap::new());
         assert!(
             result
                 .unwrap_err()
                 .to_string()
-                .contains("must be between 0.0 and 1.0")
+                .contains("Missing required argument 'value'")
         );
     }
 }
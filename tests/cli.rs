@@ -0,0 +1,270 @@
+//! End-to-end tests that run the `themer` binary against a temporary config.
+//!
+//! The config location is redirected through `XDG_CONFIG_HOME`, which is only
+//! honored by the `dirs` crate on Linux.
+#![cfg(target_os = "linux")]
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+fn setup_config(config: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let config_dir = temp_dir.path().join("themer");
+    fs::create_dir_all(config_dir.join("palettes")).unwrap();
+    fs::create_dir_all(config_dir.join("templates")).unwrap();
+    fs::write(config_dir.join("config.toml"), config).unwrap();
+    temp_dir
+}
+
+fn write_template(temp_dir: &TempDir, name: &str, content: &str) {
+    let path = temp_dir.path().join("themer").join("templates").join(name);
+    fs::write(path, content).unwrap();
+}
+
+fn themer(xdg_home: &Path, args: &[&str]) -> Output {
+    themer_command(xdg_home, args)
+        .output()
+        .expect("Failed to run themer binary")
+}
+
+fn themer_command(xdg_home: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_themer"));
+    command
+        .args(args)
+        .env("XDG_CONFIG_HOME", xdg_home)
+        .env("XDG_CACHE_HOME", xdg_home.join("cache"))
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE");
+    command
+}
+
+const VALIDATE_CONFIG: &str = r#"
+active_palette = ""
+
+[[targets]]
+name = "good"
+template = "good.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+
+[[targets]]
+name = "broken"
+template = "broken.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+"#;
+
+#[test]
+fn test_validate_single_valid_target() {
+    let temp_dir = setup_config(VALIDATE_CONFIG);
+    write_template(&temp_dir, "good.conf", "bg = {{ base00 }}");
+
+    let output = themer(temp_dir.path(), &["validate", "good"]);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("is valid"));
+}
+
+#[test]
+fn test_validate_all_reports_invalid_target() {
+    let temp_dir = setup_config(VALIDATE_CONFIG);
+    write_template(&temp_dir, "good.conf", "bg = {{ base00 }}");
+    write_template(&temp_dir, "broken.conf", "bg = {{ base00");
+
+    let output = themer(temp_dir.path(), &["validate"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 target(s) failed validation"));
+}
+
+const TWO_TARGETS_CONFIG: &str = r#"
+active_palette = ""
+
+[[targets]]
+name = "kitty"
+template = "kitty.conf"
+output = ""
+mode = "include"
+reload_cmd = ""
+
+[[targets]]
+name = "alacritty"
+template = "alacritty.toml"
+output = "~/.config/alacritty/colors.toml"
+mode = "replace"
+reload_cmd = ""
+"#;
+
+#[test]
+fn test_list_targets_plain() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+
+    let output = themer(temp_dir.path(), &["list-targets", "--format", "plain"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "kitty\nalacritty\n"
+    );
+}
+
+#[test]
+fn test_list_targets_json() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+
+    let output = themer(temp_dir.path(), &["list-targets", "--format", "json"]);
+
+    assert!(output.status.success());
+    let targets: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(targets[0]["name"], "kitty");
+    assert_eq!(targets[1]["mode"], "replace");
+}
+
+fn write_palette(temp_dir: &TempDir, name: &str) {
+    let path = temp_dir
+        .path()
+        .join("themer")
+        .join("palettes")
+        .join(format!("{}.json", name));
+    fs::write(path, format!(r#"{{"name": "{}"}}"#, name)).unwrap();
+}
+
+#[test]
+fn test_list_forced_color_has_ansi_escapes() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+    write_palette(&temp_dir, "nord");
+
+    let output = themer_command(temp_dir.path(), &["list"])
+        .env("CLICOLOR_FORCE", "1")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+}
+
+#[test]
+fn test_list_no_color_has_no_ansi_escapes() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+    write_palette(&temp_dir, "nord");
+
+    let output = themer_command(temp_dir.path(), &["list", "--no-color"])
+        .env("CLICOLOR_FORCE", "1")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("nord"));
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_list_without_config_file() {
+    let temp_dir = setup_config("");
+    fs::remove_file(temp_dir.path().join("themer").join("config.toml")).unwrap();
+    write_palette(&temp_dir, "nord");
+
+    let output = themer(temp_dir.path(), &["list", "--format", "plain"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "nord\n");
+}
+
+#[test]
+fn test_list_no_color_env_has_no_ansi_escapes() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+    write_palette(&temp_dir, "nord");
+
+    let output = themer_command(temp_dir.path(), &["list"])
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains('\x1b'));
+}
+
+#[test]
+fn test_json_errors_reports_error_chain() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+
+    let output = themer(temp_dir.path(), &["export", "missing", "--json-errors"]);
+
+    assert!(!output.status.success());
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"], "Failed to load palette 'missing'");
+    let chain = error["chain"].as_array().unwrap();
+    assert!(chain.len() >= 2);
+    assert_eq!(chain[0], error["error"]);
+}
+
+#[test]
+fn test_render_template_from_stdin() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+    let path = temp_dir
+        .path()
+        .join("themer")
+        .join("palettes")
+        .join("gray.json");
+    fs::write(
+        path,
+        r#"{"name": "gray", "base_16": {
+            "base00": "101010", "base01": "111111", "base02": "222222", "base03": "333333",
+            "base04": "444444", "base05": "555555", "base06": "666666", "base07": "777777",
+            "base08": "888888", "base09": "999999", "base0A": "aaaaaa", "base0B": "bbbbbb",
+            "base0C": "cccccc", "base0D": "dddddd", "base0E": "eeeeee", "base0F": "ffffff"
+        }}"#,
+    )
+    .unwrap();
+
+    let mut child = themer_command(temp_dir.path(), &["render", "--palette", "gray"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"{{ base00 }}")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "101010");
+}
+
+#[test]
+fn test_completion_bash_lists_subcommands() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+
+    let output = themer(temp_dir.path(), &["completion", "bash"]);
+
+    assert!(output.status.success());
+    let script = String::from_utf8_lossy(&output.stdout);
+    for subcommand in ["apply", "validate", "list-targets", "completion"] {
+        assert!(script.contains(subcommand), "{}", subcommand);
+    }
+}
+
+#[test]
+fn test_complete_palettes_lists_palette_names() {
+    let temp_dir = setup_config(TWO_TARGETS_CONFIG);
+    write_palette(&temp_dir, "nord");
+    write_palette(&temp_dir, "gruvbox");
+
+    let output = themer(temp_dir.path(), &["__complete-palettes"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "gruvbox\nnord\n");
+}